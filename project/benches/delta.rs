@@ -0,0 +1,135 @@
+//un tick cu 5000 de procese: varianta veche (tot snapshot-ul clonat, harta
+//si arborele refacute) fata de delta + Mirror. cargo bench --bench delta
+use std::collections::{HashMap, VecDeque};
+
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use project::{
+    OverallData, ProcessData, ProcessHistory, Stats, build_tree,
+    delta::{Differ, Mirror},
+};
+
+const PROCESSES: u32 = 5000;
+const HISTORY: usize = 60;
+
+fn proc(pid: u32, cpu: f32) -> ProcessData {
+    ProcessData {
+        pid,
+        name: format!("worker-{}", pid),
+        ppid: (pid > 1).then_some(pid / 8 + 1),
+        cpu_usage: cpu,
+        memory: 4096 * pid as u64,
+        path: format!("/usr/lib/app/bin/worker-{}", pid),
+        username: "www-data".to_string(),
+        is_thread: None,
+        disk_read: 0,
+        disk_write: 0,
+        cgroup: Some("/system.slice/app.service".to_string()),
+        virtual_memory: 0,
+        shared_memory: 0,
+        swap: 0,
+        start_time: 1_700_000_000 + pid as u64,
+    }
+}
+
+fn frame(procese: Vec<ProcessData>) -> OverallData {
+    OverallData {
+        procese,
+        stats: Stats {
+            global_cpu_usage: 12.0,
+            core_usage: vec![12.0; 16],
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+//a si b difera ca doua esantioane reale: 5% din procese isi schimba cpu-ul,
+//10 mor si 10 pornesc
+fn frames() -> (OverallData, OverallData) {
+    let a: Vec<ProcessData> = (1..=PROCESSES).map(|pid| proc(pid, 0.0)).collect();
+    let mut b: Vec<ProcessData> = a
+        .iter()
+        .filter(|p| p.pid <= PROCESSES - 10)
+        .cloned()
+        .map(|mut p| {
+            if p.pid % 20 == 0 {
+                p.cpu_usage = 3.5;
+            }
+            p
+        })
+        .collect();
+    b.extend((PROCESSES + 1..=PROCESSES + 10).map(|pid| proc(pid, 1.0)));
+    (frame(a), frame(b))
+}
+
+fn histories(data: &OverallData) -> (Stats, HashMap<u32, ProcessHistory>) {
+    let line = || VecDeque::from(vec![1.0; HISTORY]);
+    let stats = Stats {
+        cpu_history: line(),
+        mem_history: line(),
+        swap_history: line(),
+        load_history: std::array::from_fn(|_| line()),
+        core_history: vec![line(); 16],
+        ..data.stats.clone()
+    };
+    let per_process = data
+        .procese
+        .iter()
+        .map(|p| {
+            let h = ProcessHistory {
+                start_time: p.start_time,
+                cpu: line(),
+                memory: line(),
+            };
+            (p.pid, h)
+        })
+        .collect();
+    (stats, per_process)
+}
+
+fn tick(c: &mut Criterion) {
+    let (a, b) = frames();
+    let mut group = c.benchmark_group("tick_5000_processes");
+
+    //ce facea fiecare esantion inainte: backend-ul clona istoricele,
+    //ui-ul clona fiecare proces in harta si refacea arborele
+    let (stats, per_process) = histories(&a);
+    group.bench_function("full_snapshot", |bench| {
+        bench.iter_batched(
+            || b.clone(),
+            |mut data| {
+                data.stats = stats.clone();
+                data.process_history = per_process.clone();
+                let map: HashMap<u32, ProcessData> =
+                    data.procese.iter().map(|p| (p.pid, p.clone())).collect();
+                let tree = build_tree(&data.procese);
+                black_box((data, map, tree))
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    let mut differ = Differ::new();
+    let mut mirror = Mirror::new();
+    for _ in 0..HISTORY {
+        mirror.apply(differ.diff(a.clone(), HISTORY, 0));
+    }
+    let mut flip = false;
+    group.bench_function("delta", |bench| {
+        bench.iter_batched(
+            || {
+                flip = !flip;
+                if flip { b.clone() } else { a.clone() }
+            },
+            |data| {
+                mirror.apply(differ.diff(data, HISTORY, 0));
+                black_box(mirror.tree().roots().len())
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, tick);
+criterion_main!(benches);
@@ -0,0 +1,337 @@
+//reguli de alertare evaluate in backend la fiecare esantion
+//
+//regulile se citesc dintr-un fisier json (--alerts <fisier>, altfel
+//$XDG_CONFIG_HOME/process-monitor/alerts.json daca exista):
+//  [
+//    {"name": "cpu ridicat", "metric": "cpu", "above": 90, "for_secs": 30},
+//    {"name": "swap", "metric": "swap", "above": "50%"},
+//    {"name": "java rss", "metric": "process_memory", "match": "name:java", "above": "8GB"}
+//  ]
+//metric: cpu, memory, swap (procente din total), process_cpu (procent),
+//        process_memory (bytes, accepta unitatile din filtru, fara unitate = MB)
+//match: interogare ca in bara de filtru, doar pentru regulile per proces
+//for_secs: cat timp trebuie sa tina conditia inainte sa porneasca alerta
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    OverallData, ProcessData, bytes_to_gb,
+    filter::{Filter, parse_size},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Metric {
+    Cpu,
+    Memory,
+    Swap,
+    ProcessCpu,
+    ProcessMemory,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Threshold {
+    Number(f64),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleConfig {
+    name: String,
+    metric: Metric,
+    #[serde(rename = "match", default)]
+    matches: Option<String>,
+    above: Threshold,
+    #[serde(default)]
+    for_secs: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    pub name: String,
+    metric: Metric,
+    filter: Filter,
+    above: f64,
+    for_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AlertKind {
+    Fired,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub kind: AlertKind,
+    pub timestamp_ms: u64,
+    pub message: String,
+}
+
+#[derive(Default)]
+struct RuleState {
+    //de cand e depasit pragul, fara sa fi pornit inca
+    breaching_since: Option<u64>,
+    firing: Option<AlertEvent>,
+}
+
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    state: Vec<RuleState>,
+}
+
+pub fn parse_rules(json: &str) -> Result<Vec<AlertRule>, String> {
+    let configs: Vec<RuleConfig> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    configs
+        .into_iter()
+        .map(|c| {
+            let context = |e: String| format!("rule '{}': {}", c.name, e);
+            let per_process = matches!(c.metric, Metric::ProcessCpu | Metric::ProcessMemory);
+            let filter = match (&c.matches, per_process) {
+                (Some(q), true) => Filter::parse(q).map_err(context)?,
+                (Some(_), false) => {
+                    return Err(context("'match' only applies to process metrics".into()));
+                }
+                (None, _) => Filter::default(),
+            };
+            let above = match &c.above {
+                //fara unitate e MB si ca numar, la fel ca in forma text
+                Threshold::Number(n) if c.metric == Metric::ProcessMemory => n * 1048576.0,
+                Threshold::Number(n) => *n,
+                Threshold::Text(t) if c.metric == Metric::ProcessMemory => {
+                    parse_size(t).map_err(context)?
+                }
+                Threshold::Text(t) => t
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| context(format!("'{}' is not a number", t)))?,
+            };
+            Ok(AlertRule {
+                name: c.name,
+                metric: c.metric,
+                filter,
+                above,
+                for_secs: c.for_secs,
+            })
+        })
+        .collect()
+}
+
+pub fn load_rules(path: &Path) -> Result<Vec<AlertRule>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_rules(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn default_rules_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(base.join("process-monitor").join("alerts.json"))
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> AlertEngine {
+        let state = rules.iter().map(|_| RuleState::default()).collect();
+        AlertEngine { rules, state }
+    }
+
+    //intoarce evenimentele noi (pornite sau rezolvate) pentru esantionul curent
+    pub fn evaluate(&mut self, now_ms: u64, data: &OverallData) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        for (rule, state) in self.rules.iter().zip(&mut self.state) {
+            match breach(rule, data) {
+                Some(message) => {
+                    if state.firing.is_some() {
+                        continue;
+                    }
+                    let since = *state.breaching_since.get_or_insert(now_ms);
+                    if now_ms.saturating_sub(since) >= rule.for_secs * 1000 {
+                        let event = AlertEvent {
+                            rule: rule.name.clone(),
+                            kind: AlertKind::Fired,
+                            timestamp_ms: now_ms,
+                            message,
+                        };
+                        state.firing = Some(event.clone());
+                        events.push(event);
+                    }
+                }
+                None => {
+                    state.breaching_since = None;
+                    if let Some(fired) = state.firing.take() {
+                        events.push(AlertEvent {
+                            rule: rule.name.clone(),
+                            kind: AlertKind::Resolved,
+                            timestamp_ms: now_ms,
+                            message: fired.message,
+                        });
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    pub fn active(&self) -> Vec<AlertEvent> {
+        self.state.iter().filter_map(|s| s.firing.clone()).collect()
+    }
+}
+
+//mesajul alertei daca regula e incalcata acum
+fn breach(rule: &AlertRule, data: &OverallData) -> Option<String> {
+    let stats = &data.stats;
+    let percent = |used: u64, total: u64| {
+        if total > 0 {
+            used as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        }
+    };
+    let global = match rule.metric {
+        Metric::Cpu => Some(("cpu", stats.global_cpu_usage as f64)),
+        Metric::Memory => Some(("memory", percent(stats.used_memory, stats.total_memory))),
+        Metric::Swap => Some(("swap", percent(stats.used_swap, stats.total_swap))),
+        Metric::ProcessCpu | Metric::ProcessMemory => None,
+    };
+    if let Some((label, value)) = global {
+        return (value > rule.above)
+            .then(|| format!("{} {:.1}% > {:.1}%", label, value, rule.above));
+    }
+
+    //cel mai mare proces care trece de prag
+    let value_of = |p: &ProcessData| match rule.metric {
+        Metric::ProcessCpu => p.cpu_usage as f64,
+        _ => p.memory as f64,
+    };
+    let worst = data
+        .procese
+        .iter()
+        .filter(|p| p.is_thread.is_none() && rule.filter.matches(p))
+        .filter(|p| value_of(p) > rule.above)
+        .max_by(|a, b| value_of(a).total_cmp(&value_of(b)))?;
+    let value = value_of(worst);
+    Some(match rule.metric {
+        Metric::ProcessCpu => format!(
+            "{} [{}] cpu {:.1}% > {:.1}%",
+            worst.name, worst.pid, value, rule.above
+        ),
+        _ => format!(
+            "{} [{}] rss {:.2} GB > {:.2} GB",
+            worst.name,
+            worst.pid,
+            bytes_to_gb(worst.memory),
+            rule.above / 1073741824.0
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stats;
+
+    fn snapshot(cpu: f32, procese: Vec<ProcessData>) -> OverallData {
+        OverallData {
+            procese,
+            stats: Stats {
+                global_cpu_usage: cpu,
+                used_swap: 600,
+                total_swap: 1000,
+                ..Stats::default()
+            },
+            details: None,
+            alert_events: Vec::new(),
+            active_alerts: Vec::new(),
+            process_events: Vec::new(),
+            process_history: Default::default(),
+        }
+    }
+
+    fn java(pid: u32, memory: u64) -> ProcessData {
+        ProcessData {
+            pid,
+            name: "java".to_string(),
+            ppid: Some(1),
+            cpu_usage: 0.0,
+            memory,
+            path: "/usr/bin/java".to_string(),
+            username: "app".to_string(),
+            is_thread: None,
+            disk_read: 0,
+            disk_write: 0,
+            cgroup: None,
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            start_time: 0,
+        }
+    }
+
+    const RULES: &str = r#"[
+        {"name": "cpu", "metric": "cpu", "above": 90, "for_secs": 30},
+        {"name": "swap", "metric": "swap", "above": "50%"},
+        {"name": "java", "metric": "process_memory", "match": "name:java", "above": "8GB"},
+        {"name": "sh", "metric": "process_memory", "match": "name:sh", "above": 512}
+    ]"#;
+
+    #[test]
+    fn parses_rules_and_rejects_bad_ones() {
+        let rules = parse_rules(RULES).unwrap();
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules[0].for_secs, 30);
+        assert_eq!(rules[1].above, 50.0);
+        assert_eq!(rules[2].above, 8.0 * 1073741824.0);
+        assert_eq!(rules[3].above, 512.0 * 1048576.0);
+        assert!(parse_rules(r#"[{"name": "x", "metric": "disk", "above": 1}]"#).is_err());
+        assert!(
+            parse_rules(r#"[{"name": "x", "metric": "cpu", "match": "name:a", "above": 1}]"#)
+                .is_err()
+        );
+        assert!(
+            parse_rules(r#"[{"name": "x", "metric": "process_cpu", "match": "c>", "above": 1}]"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn cpu_rule_waits_for_duration_then_resolves() {
+        let rules = parse_rules(RULES).unwrap();
+        let mut engine = AlertEngine::new(vec![rules[0].clone()]);
+        assert!(engine.evaluate(0, &snapshot(95.0, vec![])).is_empty());
+        assert!(engine.evaluate(29_000, &snapshot(95.0, vec![])).is_empty());
+        //o scadere sub prag reseteaza durata
+        assert!(engine.evaluate(30_000, &snapshot(50.0, vec![])).is_empty());
+        assert!(engine.evaluate(31_000, &snapshot(95.0, vec![])).is_empty());
+        let fired = engine.evaluate(61_000, &snapshot(95.0, vec![]));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].kind, AlertKind::Fired);
+        assert_eq!(engine.active().len(), 1);
+        //nu se repeta cat timp e activa
+        assert!(engine.evaluate(62_000, &snapshot(99.0, vec![])).is_empty());
+        let resolved = engine.evaluate(63_000, &snapshot(10.0, vec![]));
+        assert_eq!(resolved[0].kind, AlertKind::Resolved);
+        assert!(engine.active().is_empty());
+    }
+
+    #[test]
+    fn swap_and_process_rules_fire_immediately() {
+        let rules = parse_rules(RULES).unwrap();
+        let mut engine = AlertEngine::new(rules[1..3].to_vec());
+        let gb = 1073741824;
+        let events = engine.evaluate(0, &snapshot(0.0, vec![java(5, gb), java(6, 9 * gb)]));
+        assert_eq!(events.len(), 2);
+        assert!(events[0].message.starts_with("swap 60.0%"));
+        assert!(events[1].message.starts_with("java [6]"));
+        let events = engine.evaluate(1000, &snapshot(0.0, vec![java(5, gb)]));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].rule, "java");
+        assert_eq!(events[0].kind, AlertKind::Resolved);
+    }
+}
@@ -0,0 +1,193 @@
+//citirea /proc/<pid>/cgroup si gruparea dupa unit systemd, slice sau container
+//
+//v2: o singura linie "0::/system.slice/nginx.service"
+//v1: cate o linie "N:controlere:/cale" pe ierarhie, luam name=systemd,
+//    altfel prima cale diferita de "/"
+//containerele se recunosc dupa id-ul hex din cale:
+//  /system.slice/docker-<id>.scope, /docker/<id>, /machine.slice/libpod-<id>.scope,
+//  /kubepods/.../cri-containerd-<id>.scope, /kubepods/burstable/pod<uid>/<id>
+use std::fs;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CgroupGroup {
+    Container { runtime: &'static str, id: String },
+    Unit(String),
+    Slice(String),
+    Root,
+}
+
+impl CgroupGroup {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CgroupGroup::Container { runtime, .. } => runtime,
+            CgroupGroup::Unit(_) => "unit",
+            CgroupGroup::Slice(_) => "slice",
+            CgroupGroup::Root => "root",
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            //id-ul scurt, ca in `docker ps`
+            CgroupGroup::Container { id, .. } => id.chars().take(12).collect(),
+            CgroupGroup::Unit(name) | CgroupGroup::Slice(name) => name.clone(),
+            CgroupGroup::Root => "/".to_string(),
+        }
+    }
+}
+
+pub fn read(pid: u32) -> Option<String> {
+    fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .ok()
+        .and_then(|s| parse(&s))
+}
+
+pub fn parse(contents: &str) -> Option<String> {
+    let mut fallback = None;
+    for line in contents.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(_), Some(controllers), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if controllers.is_empty() || controllers == "name=systemd" {
+            return Some(path.to_string());
+        }
+        if fallback.is_none() && path != "/" {
+            fallback = Some(path.to_string());
+        }
+    }
+    fallback.or_else(|| (!contents.trim().is_empty()).then(|| "/".to_string()))
+}
+
+pub fn classify(path: &str) -> CgroupGroup {
+    let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    //de la frunza spre radacina, un container poate avea sub-cgroup-uri proprii
+    for (i, part) in parts.iter().enumerate().rev() {
+        let parent = i.checked_sub(1).map(|j| parts[j]);
+        if let Some((runtime, id)) = container_id(part, parent) {
+            return CgroupGroup::Container {
+                runtime,
+                id: id.to_string(),
+            };
+        }
+    }
+    let is_unit = |p: &str| {
+        [".service", ".scope", ".socket", ".mount", ".swap"]
+            .iter()
+            .any(|suffix| p.ends_with(suffix))
+    };
+    if let Some(unit) = parts.iter().rev().find(|p| is_unit(p)) {
+        return CgroupGroup::Unit(unit.to_string());
+    }
+    if let Some(slice) = parts.iter().rev().find(|p| p.ends_with(".slice")) {
+        return CgroupGroup::Slice(slice.to_string());
+    }
+    match parts.last() {
+        Some(last) => CgroupGroup::Unit(last.to_string()),
+        None => CgroupGroup::Root,
+    }
+}
+
+fn container_id<'a>(part: &'a str, parent: Option<&str>) -> Option<(&'static str, &'a str)> {
+    let stem = part.strip_suffix(".scope").unwrap_or(part);
+    let prefixes = [
+        ("docker-", "docker"),
+        ("libpod-", "podman"),
+        ("cri-containerd-", "containerd"),
+        ("crio-", "cri-o"),
+    ];
+    for (prefix, runtime) in prefixes {
+        if let Some(id) = stem.strip_prefix(prefix)
+            && is_container_id(id)
+        {
+            return Some((runtime, id));
+        }
+    }
+    //driver-ul cgroupfs pune id-ul direct sub directorul runtime-ului
+    if is_container_id(part) {
+        let runtime = match parent {
+            Some("docker") => "docker",
+            Some("libpod_parent") => "podman",
+            _ => "container",
+        };
+        return Some((runtime, part));
+    }
+    None
+}
+
+fn is_container_id(s: &str) -> bool {
+    s.len() >= 32 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "3f4e5a6b7c8d9e0f1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f7081";
+
+    #[test]
+    fn parses_v2() {
+        let path = parse("0::/system.slice/nginx.service\n").unwrap();
+        assert_eq!(path, "/system.slice/nginx.service");
+        assert_eq!(
+            classify(&path),
+            CgroupGroup::Unit("nginx.service".to_string())
+        );
+
+        let docker = parse(&format!("0::/system.slice/docker-{}.scope\n", ID)).unwrap();
+        assert_eq!(
+            classify(&docker),
+            CgroupGroup::Container {
+                runtime: "docker",
+                id: ID.to_string()
+            }
+        );
+        let podman = format!("/machine.slice/libpod-{}.scope/container", ID);
+        assert_eq!(classify(&podman).kind(), "podman");
+        assert_eq!(classify(&podman).label(), &ID[..12]);
+
+        let session = "/user.slice/user-1000.slice/session-2.scope";
+        assert_eq!(
+            classify(session),
+            CgroupGroup::Unit("session-2.scope".to_string())
+        );
+        assert_eq!(
+            classify("/user.slice/user-1000.slice"),
+            CgroupGroup::Slice("user-1000.slice".to_string())
+        );
+        assert_eq!(classify(&parse("0::/\n").unwrap()), CgroupGroup::Root);
+    }
+
+    #[test]
+    fn parses_v1() {
+        let systemd = "\
+12:pids:/system.slice/cron.service
+11:memory:/system.slice/cron.service
+3:cpu,cpuacct:/system.slice/cron.service
+1:name=systemd:/system.slice/cron.service
+";
+        assert_eq!(
+            classify(&parse(systemd).unwrap()),
+            CgroupGroup::Unit("cron.service".to_string())
+        );
+
+        //docker cu driver-ul cgroupfs, fara ierarhie systemd
+        let docker = format!(
+            "12:pids:/docker/{id}\n11:memory:/docker/{id}\n2:cpuset:/\n",
+            id = ID
+        );
+        assert_eq!(
+            classify(&parse(&docker).unwrap()),
+            CgroupGroup::Container {
+                runtime: "docker",
+                id: ID.to_string()
+            }
+        );
+
+        let kube = format!("4:memory:/kubepods/burstable/pod1234-abcd/{}\n", ID);
+        assert_eq!(classify(&parse(&kube).unwrap()).kind(), "container");
+        assert_eq!(parse("2:cpuset:/\n").as_deref(), Some("/"));
+        assert_eq!(parse(""), None);
+    }
+}
@@ -0,0 +1,709 @@
+//protocolul incremental backend -> consumator. backend-ul nu mai trimite la fiecare
+//esantion toate procesele si toate istoricele, ci doar ce s-a schimbat (Differ);
+//consumatorul tine starea completa la zi (Mirror): harta pid -> proces, arborele
+//si istoricele, din punctele noi. snapshot-urile complete (replay, agent) trec
+//prin Mirror::replace sau printr-un Differ
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    OverallData, ProcessData, ProcessDetails, ProcessHistory, Stats,
+    alerts::AlertEvent,
+    lifecycle::{LifecycleKind, ProcessEvent},
+    sampler::push_history,
+    tree::ProcessTree,
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct Delta {
+    //primul delta al unui Differ: consumatorul o ia de la zero
+    pub reset: bool,
+    pub history_len: usize,
+    pub added: Vec<ProcessData>,
+    pub changed: Vec<ProcessData>,
+    pub removed: Vec<u32>,
+    //valorile curente; istoricele raman goale, se refac din ticks
+    pub stats: Stats,
+    pub ticks: Vec<Tick>,
+    pub details: Option<ProcessDetails>,
+    pub alert_events: Vec<AlertEvent>,
+    pub active_alerts: Vec<AlertEvent>,
+    pub process_events: Vec<ProcessEvent>,
+}
+
+//cat tine Mirror minte pornirile recente (pentru evidentierea randurilor noi)
+const RECENT_STARTS: Duration = Duration::from_secs(10);
+const EVENT_LOG_LEN: usize = 2000;
+
+//punctele adaugate in istorice la un esantion
+#[derive(Clone, Debug, Default)]
+pub struct Tick {
+    pub cpu: f32,
+    pub mem: f32,
+    pub swap: f32,
+    pub load: [f32; 3],
+    pub cores: Vec<f32>,
+    //nume, rx, tx (bytes/s)
+    pub net: Vec<(String, f32, f32)>,
+    pub processes: Vec<ProcessPoint>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ProcessPoint {
+    pub pid: u32,
+    //un punct al pid-ului vechi nu ajunge in istoricul celui refolosit
+    pub start_time: u64,
+    pub cpu: f32,
+    pub memory: f32,
+}
+
+impl Delta {
+    //un delta nou peste unul neconsumat inca (ui in pauza sau blocat).
+    //ordinea la aplicare e removed, added, changed, deci un pid refolosit
+    //poate sta si in removed si in added. ruleaza sub lacatul handoff-ului la
+    //fiecare esantion neconsumat, deci pid-urile se cauta prin indexuri, nu liniar
+    pub fn merge(&mut self, newer: Delta) {
+        if newer.reset {
+            let mut events = std::mem::take(&mut self.alert_events);
+            events.extend(newer.alert_events.iter().cloned());
+            let mut lifecycle = std::mem::take(&mut self.process_events);
+            lifecycle.extend(newer.process_events.iter().cloned());
+            *self = Delta {
+                alert_events: events,
+                process_events: lifecycle,
+                ..newer
+            };
+            self.trim_events();
+            return;
+        }
+        if !newer.removed.is_empty() {
+            let pending: HashSet<u32> = self.added.iter().map(|p| p.pid).collect();
+            let removed: HashSet<u32> = newer.removed.iter().copied().collect();
+            //adaugat si sters inainte sa-l vada consumatorul
+            self.added.retain(|p| !removed.contains(&p.pid));
+            self.changed.retain(|p| !removed.contains(&p.pid));
+            let gone = newer
+                .removed
+                .into_iter()
+                .filter(|pid| !pending.contains(pid));
+            self.removed.extend(gone);
+        }
+        self.added.extend(newer.added);
+        let index = |v: &[ProcessData]| -> HashMap<u32, usize> {
+            v.iter().enumerate().map(|(i, p)| (p.pid, i)).collect()
+        };
+        let added = index(&self.added);
+        let mut changed = index(&self.changed);
+        for p in newer.changed {
+            if let Some(&i) = added.get(&p.pid) {
+                self.added[i] = p;
+            } else if let Some(&i) = changed.get(&p.pid) {
+                self.changed[i] = p;
+            } else {
+                changed.insert(p.pid, self.changed.len());
+                self.changed.push(p);
+            }
+        }
+        self.history_len = newer.history_len;
+        self.stats = newer.stats;
+        self.ticks.extend(newer.ticks);
+        //punctele mai vechi decat fereastra oricum s-ar arunca
+        let extra = self.ticks.len().saturating_sub(self.history_len.max(1));
+        self.ticks.drain(..extra);
+        self.details = newer.details;
+        self.alert_events.extend(newer.alert_events);
+        self.active_alerts = newer.active_alerts;
+        self.process_events.extend(newer.process_events);
+        self.trim_events();
+    }
+
+    //oricum Mirror nu tine mai mult de atat; un host care face fork-uri des ar
+    //umfla delta-ul cat timp ui-ul e minimizat sau in pauza
+    fn trim_events(&mut self) {
+        let extra = self.process_events.len().saturating_sub(EVENT_LOG_LEN);
+        self.process_events.drain(..extra);
+    }
+}
+
+//partea backend-ului: tine procesele esantionului anterior si scoate diferenta
+#[derive(Default)]
+pub struct Differ {
+    prev: Vec<ProcessData>,
+    index: HashMap<u32, usize>,
+    started: bool,
+}
+
+impl Differ {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //data vine fara istorice; procesele ei devin referinta pentru urmatorul esantion.
+    //la primul esantion nu exista pornituri, tot ce ruleaza era deja acolo
+    pub fn diff(&mut self, data: OverallData, history_len: usize, now_ms: u64) -> Delta {
+        let mut index = HashMap::with_capacity(data.procese.len());
+        let (mut added, mut changed) = (Vec::new(), Vec::new());
+        let mut started = Vec::new();
+        let mut exited = Vec::new();
+        for (i, p) in data.procese.iter().enumerate() {
+            index.insert(p.pid, i);
+            match self.index.get(&p.pid) {
+                None => {
+                    added.push(p.clone());
+                    started.push(p);
+                }
+                Some(&j) if self.prev[j] != *p => {
+                    changed.push(p.clone());
+                    if self.prev[j].start_time != p.start_time {
+                        exited.push(&self.prev[j]);
+                        started.push(p);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        let removed: Vec<u32> = self
+            .index
+            .keys()
+            .filter(|pid| !index.contains_key(pid))
+            .copied()
+            .collect();
+        exited.extend(removed.iter().map(|pid| &self.prev[self.index[pid]]));
+        let process_events = if self.started {
+            let event = |kind| move |p: &&ProcessData| ProcessEvent::new(kind, now_ms, p);
+            let exits = exited.iter().filter(|p| ProcessEvent::tracked(p));
+            let starts = started.iter().filter(|p| ProcessEvent::tracked(p));
+            exits
+                .map(event(LifecycleKind::Exited))
+                .chain(starts.map(event(LifecycleKind::Started)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let tick = Tick::from_data(&data);
+        self.prev = data.procese;
+        self.index = index;
+        let reset = !self.started;
+        self.started = true;
+        Delta {
+            reset,
+            history_len,
+            added,
+            changed,
+            removed,
+            stats: data.stats,
+            ticks: vec![tick],
+            details: data.details,
+            alert_events: data.alert_events,
+            active_alerts: data.active_alerts,
+            process_events,
+        }
+    }
+}
+
+impl Tick {
+    fn from_data(data: &OverallData) -> Self {
+        let s = &data.stats;
+        let percent = |used: u64, total: u64| {
+            if total > 0 {
+                (used as f32 / total as f32) * 100.0
+            } else {
+                0.0
+            }
+        };
+        Tick {
+            cpu: s.global_cpu_usage,
+            mem: percent(s.used_memory, s.total_memory),
+            swap: percent(s.used_swap, s.total_swap),
+            load: s.load_average.map(|l| l as f32),
+            cores: s.core_usage.clone(),
+            net: s
+                .networks
+                .iter()
+                .map(|n| (n.name.clone(), n.rx_rate as f32, n.tx_rate as f32))
+                .collect(),
+            processes: data
+                .procese
+                .iter()
+                .map(|p| ProcessPoint {
+                    pid: p.pid,
+                    start_time: p.start_time,
+                    cpu: p.cpu_usage,
+                    memory: p.memory as f32,
+                })
+                .collect(),
+        }
+    }
+}
+
+//partea consumatorului: starea completa, actualizata pe loc din delta-uri
+#[derive(Default)]
+pub struct Mirror {
+    data: Option<OverallData>,
+    index: HashMap<u32, usize>,
+    tree: ProcessTree,
+    //log-ul de pornire/iesire, cele mai vechi ies primele
+    events: VecDeque<ProcessEvent>,
+    recent_starts: HashMap<u32, Instant>,
+}
+
+impl Mirror {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //None pana la primul delta
+    pub fn data(&self) -> Option<&OverallData> {
+        self.data.as_ref()
+    }
+
+    pub fn get(&self, pid: u32) -> Option<&ProcessData> {
+        let data = self.data.as_ref()?;
+        self.index.get(&pid).map(|&i| &data.procese[i])
+    }
+
+    pub fn tree(&self) -> &ProcessTree {
+        &self.tree
+    }
+
+    pub fn events(&self) -> &VecDeque<ProcessEvent> {
+        &self.events
+    }
+
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    //procesul a pornit de curand (vazut intr-un delta, nu era in primul esantion)
+    pub fn started_within(&self, pid: u32, window: Duration) -> bool {
+        self.recent_starts
+            .get(&pid)
+            .is_some_and(|t| t.elapsed() < window)
+    }
+
+    //log-ul de evenimente ramane, e istoria gazdei, nu a conexiunii
+    pub fn clear(&mut self) {
+        self.data = None;
+        self.index.clear();
+        self.tree.clear();
+        self.recent_starts.clear();
+    }
+
+    //un snapshot complet (cadru de replay): totul se reconstruieste
+    pub fn replace(&mut self, data: OverallData) {
+        self.index = data
+            .procese
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.pid, i))
+            .collect();
+        self.tree = ProcessTree::build(&data.procese);
+        self.data = Some(data);
+    }
+
+    pub fn apply(&mut self, delta: Delta) {
+        if delta.reset {
+            self.clear();
+        }
+        let data = self.data.get_or_insert_with(OverallData::default);
+        let index = &mut self.index;
+        let tree = &mut self.tree;
+
+        for pid in delta.removed {
+            remove(data, index, tree, pid);
+            self.recent_starts.remove(&pid);
+        }
+        self.recent_starts
+            .retain(|_, t| t.elapsed() < RECENT_STARTS);
+        let now = Instant::now();
+        for e in &delta.process_events {
+            if e.kind == LifecycleKind::Started {
+                self.recent_starts.insert(e.process.pid, now);
+            }
+        }
+        self.events.extend(delta.process_events.iter().cloned());
+        let extra = self.events.len().saturating_sub(EVENT_LOG_LEN);
+        self.events.drain(..extra);
+        for p in delta.added {
+            let pid = p.pid;
+            //pid refolosit intr-un delta comasat, fara removed intre ele
+            remove(data, index, tree, pid);
+            index.insert(pid, data.procese.len());
+            data.procese.push(p);
+            tree.insert(pid, |pid| index.get(&pid).map(|&i| &data.procese[i]));
+        }
+        for p in delta.changed {
+            let pid = p.pid;
+            let Some(&i) = index.get(&pid) else {
+                continue;
+            };
+            let old = &data.procese[i];
+            if old.start_time != p.start_time {
+                remove(data, index, tree, pid);
+                index.insert(pid, data.procese.len());
+                data.procese.push(p);
+                tree.insert(pid, |pid| index.get(&pid).map(|&i| &data.procese[i]));
+                continue;
+            }
+            let reparented = old.ppid != p.ppid;
+            data.procese[i] = p;
+            if reparented {
+                tree.relink(pid, |pid| index.get(&pid).map(|&i| &data.procese[i]));
+            }
+        }
+
+        //un snapshot complet (agentul) vine cu istoricele lui, deja cu punctul curent:
+        //la reset le pastram pe acelea in loc sa pornim graficele de la zero
+        let seeded = delta.reset && !delta.stats.cpu_history.is_empty();
+        //altfel istoricele trec din statisticile vechi in cele noi, fara copii
+        let len = delta.history_len;
+        let mut old = std::mem::replace(&mut data.stats, delta.stats);
+        let stats = &mut data.stats;
+        if !seeded {
+            stats.cpu_history = old.cpu_history;
+            stats.mem_history = old.mem_history;
+            stats.swap_history = old.swap_history;
+            stats.load_history = old.load_history;
+            stats.core_history = old.core_history;
+            for net in &mut stats.networks {
+                if let Some(o) = old.networks.iter_mut().find(|o| o.name == net.name) {
+                    net.rx_history = std::mem::take(&mut o.rx_history);
+                    net.tx_history = std::mem::take(&mut o.tx_history);
+                }
+            }
+        }
+        for tick in delta.ticks {
+            //punctele per proces nu vin de la agent, se strang oricum
+            if !seeded {
+                push_global(&mut stats.cpu_history, tick.cpu, len);
+                push_global(&mut stats.mem_history, tick.mem, len);
+                push_global(&mut stats.swap_history, tick.swap, len);
+                for (h, &l) in stats.load_history.iter_mut().zip(&tick.load) {
+                    push_global(h, l, len);
+                }
+                stats
+                    .core_history
+                    .resize_with(tick.cores.len(), Default::default);
+                for (h, &usage) in stats.core_history.iter_mut().zip(&tick.cores) {
+                    push_global(h, usage, len);
+                }
+                for (name, rx, tx) in &tick.net {
+                    if let Some(net) = stats.networks.iter_mut().find(|n| &n.name == name) {
+                        push_global(&mut net.rx_history, *rx, len);
+                        push_global(&mut net.tx_history, *tx, len);
+                    }
+                }
+            }
+            for point in &tick.processes {
+                if let Some(&i) = index.get(&point.pid)
+                    && data.procese[i].start_time == point.start_time
+                {
+                    let h = data.process_history.entry(point.pid).or_default();
+                    if h.start_time != point.start_time {
+                        *h = ProcessHistory {
+                            start_time: point.start_time,
+                            ..Default::default()
+                        };
+                    }
+                    push_history(&mut h.cpu, point.cpu, len);
+                    push_history(&mut h.memory, point.memory, len);
+                }
+            }
+        }
+
+        data.details = delta.details;
+        data.alert_events = delta.alert_events;
+        data.active_alerts = delta.active_alerts;
+        data.process_events = delta.process_events;
+    }
+}
+
+//graficele globale pornesc pline de zerouri, ca sa nu se intinda primele puncte
+fn push_global(history: &mut VecDeque<f32>, value: f32, len: usize) {
+    if history.is_empty() {
+        history.resize(len, 0.0);
+    }
+    push_history(history, value, len);
+}
+
+//swap_remove tine vectorul compact; procesul mutat de la coada isi schimba indexul
+fn remove(
+    data: &mut OverallData,
+    index: &mut HashMap<u32, usize>,
+    tree: &mut ProcessTree,
+    pid: u32,
+) {
+    let Some(i) = index.remove(&pid) else {
+        return;
+    };
+    data.procese.swap_remove(i);
+    if let Some(moved) = data.procese.get(i) {
+        index.insert(moved.pid, i);
+    }
+    data.process_history.remove(&pid);
+    tree.remove(pid);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: u32, ppid: Option<u32>, cpu: f32, start_time: u64) -> ProcessData {
+        ProcessData {
+            pid,
+            name: format!("p{}", pid),
+            ppid,
+            cpu_usage: cpu,
+            memory: 10,
+            path: String::new(),
+            username: "root".to_string(),
+            is_thread: None,
+            disk_read: 0,
+            disk_write: 0,
+            cgroup: None,
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            start_time,
+        }
+    }
+
+    fn frame(procese: Vec<ProcessData>) -> OverallData {
+        OverallData {
+            procese,
+            ..Default::default()
+        }
+    }
+
+    //procese sortate, parintele din arbore si istoricul cpu al fiecaruia
+    fn view(m: &Mirror) -> Vec<(u32, Option<u32>, f32, Vec<f32>)> {
+        let data = m.data().unwrap();
+        let mut v: Vec<_> = data
+            .procese
+            .iter()
+            .map(|p| {
+                let h = data.process_history[&p.pid].cpu.iter().copied().collect();
+                (p.pid, m.tree().parent(p.pid), p.cpu_usage, h)
+            })
+            .collect();
+        v.sort_by_key(|x| x.0);
+        v
+    }
+
+    fn frames() -> Vec<OverallData> {
+        vec![
+            frame(vec![proc(1, None, 1.0, 1), proc(2, Some(1), 2.0, 2)]),
+            //3 apare, 2 se schimba
+            frame(vec![
+                proc(1, None, 1.0, 1),
+                proc(2, Some(1), 5.0, 2),
+                proc(3, Some(2), 0.0, 3),
+            ]),
+            //2 moare, 3 e preluat de 1, 4 apare
+            frame(vec![
+                proc(1, None, 1.0, 1),
+                proc(3, Some(1), 0.0, 3),
+                proc(4, Some(3), 7.0, 4),
+            ]),
+            //4 moare si pid-ul lui e refolosit, 5 vine si pleaca intre doua citiri
+            frame(vec![
+                proc(1, None, 2.0, 1),
+                proc(3, Some(1), 0.0, 3),
+                proc(4, Some(1), 9.0, 8),
+                proc(5, Some(1), 0.0, 8),
+            ]),
+            frame(vec![
+                proc(1, None, 2.0, 1),
+                proc(3, Some(1), 1.0, 3),
+                proc(4, Some(1), 9.0, 8),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn deltas_keep_the_mirror_in_sync() {
+        let mut differ = Differ::new();
+        let mut mirror = Mirror::new();
+        for f in frames() {
+            let full = f.clone();
+            mirror.apply(differ.diff(f, 10, 0));
+            let mut rebuilt = Mirror::new();
+            rebuilt.replace(full);
+            let parents = |m: &Mirror| -> Vec<(u32, Option<u32>)> {
+                let mut v: Vec<_> = m
+                    .data()
+                    .unwrap()
+                    .procese
+                    .iter()
+                    .map(|p| (p.pid, m.tree().parent(p.pid)))
+                    .collect();
+                v.sort();
+                v
+            };
+            assert_eq!(parents(&mirror), parents(&rebuilt));
+        }
+        let v = view(&mirror);
+        assert_eq!(v[0], (1, None, 2.0, vec![1.0, 1.0, 1.0, 2.0, 2.0]));
+        //pid-ul refolosit isi incepe istoricul de la zero
+        assert_eq!(v[2], (4, Some(1), 9.0, vec![9.0, 9.0]));
+    }
+
+    #[test]
+    fn merged_deltas_apply_like_separate_ones() {
+        let mut differ = Differ::new();
+        let mut separate = Mirror::new();
+        let mut merged = Mirror::new();
+        let mut pending: Option<Delta> = None;
+        for (i, f) in frames().into_iter().enumerate() {
+            let mut delta = differ.diff(f, 10, 0);
+            delta.alert_events = vec![AlertEvent {
+                rule: i.to_string(),
+                kind: crate::alerts::AlertKind::Fired,
+                timestamp_ms: 0,
+                message: String::new(),
+            }];
+            separate.apply(delta.clone());
+            //primul se aplica direct, restul stau in asteptare ca intr-un ui in pauza
+            match (i, &mut pending) {
+                (0, _) => merged.apply(delta),
+                (_, Some(p)) => p.merge(delta),
+                (_, None) => pending = Some(delta),
+            }
+        }
+        let pending = pending.unwrap();
+        //5 a venit si a plecat, consumatorul nu-l vede deloc
+        assert!(pending.added.iter().all(|p| p.pid != 5));
+        assert!(!pending.removed.contains(&5));
+        let rules: Vec<&str> = pending
+            .alert_events
+            .iter()
+            .map(|e| e.rule.as_str())
+            .collect();
+        assert_eq!(rules, ["1", "2", "3", "4"]);
+        merged.apply(pending);
+        assert_eq!(view(&merged), view(&separate));
+    }
+
+    //un ui minimizat pe o gazda mare: mii de pid-uri care se schimba, mor si
+    //se refolosesc, stranse intr-un singur delta
+    #[test]
+    fn merging_thousands_of_pids_matches_separate_deltas() {
+        let tick = |n: u32| {
+            let procs = (1..=3000)
+                .filter(|pid| !(pid + n).is_multiple_of(7))
+                .map(|pid| {
+                    //pid-ul care revine e alt proces; multiplii lui 11 se refolosesc pe loc
+                    let start = if pid.is_multiple_of(11) {
+                        100 + n as u64
+                    } else {
+                        ((pid + n) / 7) as u64
+                    };
+                    proc(
+                        pid,
+                        (pid > 1).then_some(pid / 8 + 1),
+                        ((pid * n) % 13) as f32,
+                        start,
+                    )
+                })
+                .collect();
+            frame(procs)
+        };
+        let mut differ = Differ::new();
+        let mut separate = Mirror::new();
+        let mut merged = Mirror::new();
+        let first = differ.diff(tick(0), 10, 0);
+        separate.apply(first.clone());
+        merged.apply(first);
+        let mut pending = Delta::default();
+        for n in 1..=10 {
+            let delta = differ.diff(tick(n), 10, 0);
+            separate.apply(delta.clone());
+            pending.merge(delta);
+        }
+        merged.apply(pending);
+        assert_eq!(view(&merged), view(&separate));
+    }
+
+    #[test]
+    fn lifecycle_events_follow_pid_and_start_time() {
+        let mut differ = Differ::new();
+        let mut mirror = Mirror::new();
+        for (i, f) in frames().into_iter().enumerate() {
+            mirror.apply(differ.diff(f, 10, i as u64 * 1000));
+        }
+        let log: Vec<(u64, LifecycleKind, u32, u64)> = mirror
+            .events()
+            .iter()
+            .map(|e| (e.timestamp_ms, e.kind, e.process.pid, e.process.start_time))
+            .collect();
+        use LifecycleKind::*;
+        //primul esantion nu produce pornituri
+        assert_eq!(
+            log,
+            [
+                (1000, Started, 3, 3),
+                (2000, Exited, 2, 2),
+                (2000, Started, 4, 4),
+                (3000, Exited, 4, 4),
+                (3000, Started, 4, 8),
+                (3000, Started, 5, 8),
+                (4000, Exited, 5, 8),
+            ]
+        );
+        //la iesire ramane ultima stare vazuta
+        let exit = &mirror.events()[3];
+        assert_eq!((exit.process.cpu_usage, exit.process.ppid), (7.0, Some(3)));
+        assert!(mirror.started_within(4, Duration::from_secs(5)));
+        assert!(!mirror.started_within(1, Duration::from_secs(5)));
+        assert!(!mirror.started_within(5, Duration::from_secs(5)));
+    }
+
+    //cadrele agentului au istoricele lui; o conexiune noua nu porneste de la zero
+    #[test]
+    fn reset_keeps_snapshot_histories() {
+        let agent = |cpu: &[f32]| OverallData {
+            stats: Stats {
+                global_cpu_usage: *cpu.last().unwrap(),
+                cpu_history: cpu.iter().copied().collect(),
+                ..Default::default()
+            },
+            ..frame(vec![proc(1, None, 1.0, 1)])
+        };
+        let mut differ = Differ::new();
+        let mut mirror = Mirror::new();
+        mirror.apply(differ.diff(agent(&[1.0, 2.0, 3.0]), 3, 0));
+        let cpu = |m: &Mirror| -> Vec<f32> {
+            m.data()
+                .unwrap()
+                .stats
+                .cpu_history
+                .iter()
+                .copied()
+                .collect()
+        };
+        assert_eq!(cpu(&mirror), [1.0, 2.0, 3.0]);
+        mirror.apply(differ.diff(agent(&[2.0, 3.0, 4.0]), 3, 0));
+        assert_eq!(cpu(&mirror), [2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn pending_lifecycle_events_are_capped() {
+        let event = |pid| ProcessEvent::new(LifecycleKind::Started, 0, &proc(pid, None, 0.0, 1));
+        let mut pending = Delta::default();
+        for batch in 0..3 {
+            let base = batch * EVENT_LOG_LEN as u32;
+            pending.merge(Delta {
+                process_events: (base..base + EVENT_LOG_LEN as u32).map(event).collect(),
+                ..Default::default()
+            });
+        }
+        assert_eq!(pending.process_events.len(), EVENT_LOG_LEN);
+        //raman cele mai noi
+        assert_eq!(
+            pending.process_events[0].process.pid,
+            2 * EVENT_LOG_LEN as u32
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//limbaj de filtrare pentru lista de procese
+//
+//termenii sunt separati prin spatii si trebuie sa fie toti adevarati:
+//  name~fire      numele contine "fire" (case insensitive)
+//  name:firefox   numele e exact "firefox"
+//  user:root      la fel pentru user
+//  path:/usr/bin  calea e /usr/bin sau ceva sub /usr/bin, path~bin cauta subsir
+//  cpu>5          comparatii numerice: > >= < <= = (sau :)
+//  mem>200MB      unitati B, KB, MB, GB (fara unitate inseamna MB)
+//  pid:42 ppid:1
+//  firefox        un cuvant simplu e tratat ca name~firefox
+use crate::ProcessData;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TextField {
+    Name,
+    User,
+    Path,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumField {
+    Pid,
+    Ppid,
+    Cpu,
+    Mem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Contains(TextField, String),
+    Equals(TextField, String),
+    Num(NumField, Cmp, f64),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    terms: Vec<Term>,
+}
+
+impl Filter {
+    pub fn parse(query: &str) -> Result<Filter, String> {
+        let terms = query
+            .split_whitespace()
+            .map(parse_term)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Filter { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, p: &ProcessData) -> bool {
+        self.terms.iter().all(|t| term_matches(t, p))
+    }
+}
+
+fn parse_term(tok: &str) -> Result<Term, String> {
+    //operatorii de doua caractere primii ca sa nu prindem ">" din ">="
+    let ops = [">=", "<=", ">", "<", "=", ":", "~"];
+    let found = tok.char_indices().find_map(|(i, _)| {
+        ops.iter()
+            .find(|op| tok[i..].starts_with(*op))
+            .map(|op| (i, *op))
+    });
+
+    let Some((i, op)) = found else {
+        return Ok(Term::Contains(TextField::Name, tok.to_lowercase()));
+    };
+    let key = tok[..i].to_lowercase();
+    let value = &tok[i + op.len()..];
+    if key.is_empty() {
+        return Err(format!("missing field in '{}'", tok));
+    }
+    if value.is_empty() {
+        return Err(format!("missing value in '{}'", tok));
+    }
+
+    let text_field = match key.as_str() {
+        "name" => Some(TextField::Name),
+        "user" => Some(TextField::User),
+        "path" => Some(TextField::Path),
+        _ => None,
+    };
+    if let Some(field) = text_field {
+        return match op {
+            "~" => Ok(Term::Contains(field, value.to_lowercase())),
+            ":" | "=" => Ok(Term::Equals(field, value.to_lowercase())),
+            _ => Err(format!("'{}' cannot be used with {}", op, key)),
+        };
+    }
+
+    let num_field = match key.as_str() {
+        "pid" => NumField::Pid,
+        "ppid" => NumField::Ppid,
+        "cpu" => NumField::Cpu,
+        "mem" => NumField::Mem,
+        _ => return Err(format!("unknown field '{}'", key)),
+    };
+    let cmp = match op {
+        ">" => Cmp::Gt,
+        ">=" => Cmp::Ge,
+        "<" => Cmp::Lt,
+        "<=" => Cmp::Le,
+        ":" | "=" => Cmp::Eq,
+        _ => return Err(format!("'{}' cannot be used with {}", op, key)),
+    };
+    let number = if num_field == NumField::Mem {
+        parse_size(value)?
+    } else {
+        value
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("'{}' is not a number", value))?
+    };
+    Ok(Term::Num(num_field, cmp, number))
+}
+
+//intoarce bytes
+pub fn parse_size(value: &str) -> Result<f64, String> {
+    let upper = value.to_uppercase();
+    let split = upper
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(upper.len());
+    let (num, unit) = upper.split_at(split);
+    let num: f64 = num
+        .parse()
+        .map_err(|_| format!("'{}' is not a size", value))?;
+    let mult = match unit {
+        "B" => 1.0,
+        "K" | "KB" => 1024.0,
+        "" | "M" | "MB" => 1048576.0,
+        "G" | "GB" => 1073741824.0,
+        _ => return Err(format!("unknown unit '{}'", unit)),
+    };
+    Ok(num * mult)
+}
+
+fn term_matches(term: &Term, p: &ProcessData) -> bool {
+    match term {
+        Term::Contains(field, needle) => text_of(*field, p).to_lowercase().contains(needle),
+        Term::Equals(TextField::Path, dir) => {
+            let path = p.path.to_lowercase();
+            let dir = dir.trim_end_matches('/');
+            path == dir
+                || path
+                    .strip_prefix(dir)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        }
+        Term::Equals(field, value) => text_of(*field, p).to_lowercase() == *value,
+        Term::Num(field, cmp, value) => {
+            let actual = match field {
+                NumField::Pid => p.pid as f64,
+                NumField::Ppid => match p.ppid {
+                    Some(ppid) => ppid as f64,
+                    None => return false,
+                },
+                NumField::Cpu => p.cpu_usage as f64,
+                NumField::Mem => p.memory as f64,
+            };
+            match cmp {
+                Cmp::Lt => actual < *value,
+                Cmp::Le => actual <= *value,
+                Cmp::Gt => actual > *value,
+                Cmp::Ge => actual >= *value,
+                Cmp::Eq => actual == *value,
+            }
+        }
+    }
+}
+
+fn text_of(field: TextField, p: &ProcessData) -> &str {
+    match field {
+        TextField::Name => &p.name,
+        TextField::User => &p.username,
+        TextField::Path => &p.path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(name: &str, user: &str, path: &str, cpu: f32, memory: u64) -> ProcessData {
+        ProcessData {
+            pid: 100,
+            name: name.to_string(),
+            ppid: Some(1),
+            cpu_usage: cpu,
+            memory,
+            path: path.to_string(),
+            username: user.to_string(),
+            is_thread: None,
+            disk_read: 0,
+            disk_write: 0,
+            cgroup: None,
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            start_time: 0,
+        }
+    }
+
+    #[test]
+    fn parses_example_query() {
+        let f = Filter::parse("name~firefox user:root cpu>5 mem>200MB path:/usr/bin").unwrap();
+        assert_eq!(
+            f.terms,
+            vec![
+                Term::Contains(TextField::Name, "firefox".to_string()),
+                Term::Equals(TextField::User, "root".to_string()),
+                Term::Num(NumField::Cpu, Cmp::Gt, 5.0),
+                Term::Num(NumField::Mem, Cmp::Gt, 200.0 * 1048576.0),
+                Term::Equals(TextField::Path, "/usr/bin".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn two_char_operators_and_units() {
+        let f = Filter::parse("cpu>=2.5 mem<=1G pid=7 mem<512").unwrap();
+        assert_eq!(
+            f.terms,
+            vec![
+                Term::Num(NumField::Cpu, Cmp::Ge, 2.5),
+                Term::Num(NumField::Mem, Cmp::Le, 1073741824.0),
+                Term::Num(NumField::Pid, Cmp::Eq, 7.0),
+                Term::Num(NumField::Mem, Cmp::Lt, 512.0 * 1048576.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn bare_word_is_name_search() {
+        let f = Filter::parse("Fire").unwrap();
+        assert_eq!(
+            f.terms,
+            vec![Term::Contains(TextField::Name, "fire".to_string())]
+        );
+        assert!(Filter::parse("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_terms() {
+        assert!(Filter::parse("color:red").is_err());
+        assert!(Filter::parse("cpu>lots").is_err());
+        assert!(Filter::parse("mem>5TB").is_err());
+        assert!(Filter::parse("name>5").is_err());
+        assert!(Filter::parse("cpu~5").is_err());
+        assert!(Filter::parse("cpu>").is_err());
+        assert!(Filter::parse(":root").is_err());
+    }
+
+    #[test]
+    fn matches_process_fields() {
+        let p = proc(
+            "firefox-bin",
+            "root",
+            "/usr/bin/firefox",
+            12.0,
+            300 * 1048576,
+        );
+        assert!(
+            Filter::parse("name~FIREFOX user:root cpu>5 mem>200MB path:/usr/bin")
+                .unwrap()
+                .matches(&p)
+        );
+        assert!(!Filter::parse("name:firefox").unwrap().matches(&p));
+        assert!(!Filter::parse("cpu<5").unwrap().matches(&p));
+        assert!(!Filter::parse("mem>1GB").unwrap().matches(&p));
+        assert!(Filter::parse("ppid:1").unwrap().matches(&p));
+    }
+
+    #[test]
+    fn path_equals_is_directory_prefix() {
+        let p = proc("ls", "me", "/usr/bin/ls", 0.0, 0);
+        assert!(Filter::parse("path:/usr/bin/").unwrap().matches(&p));
+        assert!(Filter::parse("path:/usr").unwrap().matches(&p));
+        assert!(!Filter::parse("path:/usr/bi").unwrap().matches(&p));
+        assert!(Filter::parse("path~/usr/bi").unwrap().matches(&p));
+    }
+}
@@ -0,0 +1,228 @@
+//agregarea proceselor pe grupuri (cgroup, user, executabil), folosita de gui si de tui
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ProcessData, cgroup, filter::Filter};
+
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum GroupBy {
+    Cgroup,
+    User,
+    Name,
+    Path,
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 4] = [GroupBy::Cgroup, GroupBy::User, GroupBy::Name, GroupBy::Path];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::Cgroup => "Cgroup",
+            GroupBy::User => "User",
+            GroupBy::Name => "Executable name",
+            GroupBy::Path => "Executable path",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum GroupSort {
+    Name,
+    Kind,
+    Count,
+    Threads,
+    Cpu,
+    Memory,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProcessGroup {
+    pub name: String,
+    //doar la cgroup: unit, slice, docker...
+    pub kind: String,
+    //indici in lista de procese
+    pub members: Vec<usize>,
+    pub threads: usize,
+    pub cpu: f32,
+    pub memory: u64,
+}
+
+//thread-urile nu sunt membri, se numara la grupul procesului lor
+pub fn group(processes: &[ProcessData], by: GroupBy, filter: Option<&Filter>) -> Vec<ProcessGroup> {
+    let mut groups: Vec<ProcessGroup> = Vec::new();
+    let mut index: HashMap<(String, String), usize> = HashMap::new();
+    let mut group_of_pid: HashMap<u32, usize> = HashMap::new();
+    for (i, p) in processes.iter().enumerate() {
+        if p.is_thread.is_some() || filter.is_some_and(|f| !f.matches(p)) {
+            continue;
+        }
+        let (kind, name) = key(p, by);
+        let gi = *index
+            .entry((kind.clone(), name.clone()))
+            .or_insert_with(|| {
+                groups.push(ProcessGroup {
+                    name,
+                    kind,
+                    members: Vec::new(),
+                    threads: 0,
+                    cpu: 0.0,
+                    memory: 0,
+                });
+                groups.len() - 1
+            });
+        let g = &mut groups[gi];
+        g.members.push(i);
+        g.cpu += p.cpu_usage;
+        g.memory += p.memory;
+        group_of_pid.insert(p.pid, gi);
+    }
+    for p in processes {
+        if p.is_thread == Some(sysinfo::ThreadKind::Userland)
+            && let Some(&gi) = p.ppid.and_then(|ppid| group_of_pid.get(&ppid))
+        {
+            groups[gi].threads += 1;
+        }
+    }
+    groups
+}
+
+fn key(p: &ProcessData, by: GroupBy) -> (String, String) {
+    match by {
+        GroupBy::Cgroup => match &p.cgroup {
+            Some(path) => {
+                let g = cgroup::classify(path);
+                (g.kind().to_string(), g.label())
+            }
+            None => ("unknown".to_string(), "?".to_string()),
+        },
+        GroupBy::User => (String::new(), p.username.clone()),
+        GroupBy::Name => (String::new(), p.name.clone()),
+        GroupBy::Path => (String::new(), p.path.clone()),
+    }
+}
+
+pub fn sort_groups(groups: &mut [ProcessGroup], col: GroupSort, desc: bool) {
+    groups.sort_by(|a, b| {
+        let cmp = match col {
+            GroupSort::Name => a.name.cmp(&b.name),
+            GroupSort::Kind => a.kind.cmp(&b.kind).then_with(|| a.name.cmp(&b.name)),
+            GroupSort::Count => a.members.len().cmp(&b.members.len()),
+            GroupSort::Threads => a.threads.cmp(&b.threads),
+            GroupSort::Cpu => a.cpu.total_cmp(&b.cpu),
+            GroupSort::Memory => a.memory.cmp(&b.memory),
+        };
+        if desc { cmp.reverse() } else { cmp }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sysinfo::ThreadKind;
+
+    fn proc(pid: u32, name: &str, user: &str, cgroup: &str, cpu: f32, memory: u64) -> ProcessData {
+        ProcessData {
+            pid,
+            name: name.to_string(),
+            ppid: Some(1),
+            cpu_usage: cpu,
+            memory,
+            path: format!("/usr/bin/{}", name),
+            username: user.to_string(),
+            is_thread: None,
+            disk_read: 0,
+            disk_write: 0,
+            cgroup: Some(cgroup.to_string()),
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            start_time: 0,
+        }
+    }
+
+    fn thread(pid: u32, ppid: u32, kind: ThreadKind) -> ProcessData {
+        ProcessData {
+            ppid: Some(ppid),
+            is_thread: Some(kind),
+            ..proc(pid, "worker", "nobody", "/", 50.0, 1000)
+        }
+    }
+
+    const DOCKER: &str = "/system.slice/docker-4f2a9c1e8b7d6a5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d4c3b2a1f.scope";
+
+    fn processes() -> Vec<ProcessData> {
+        vec![
+            proc(1, "nginx", "root", "/system.slice/nginx.service", 1.0, 100),
+            proc(2, "nginx", "www", "/system.slice/nginx.service", 2.0, 200),
+            proc(3, "bash", "alice", DOCKER, 4.0, 50),
+            //thread-urile nu sunt membri, se numara la grupul parintelui
+            thread(4, 2, ThreadKind::Userland),
+            thread(5, 3, ThreadKind::Userland),
+            thread(6, 1, ThreadKind::Kernel),
+            proc(
+                7,
+                "vim",
+                "alice",
+                "/user.slice/user-1000.slice/session-2.scope",
+                0.5,
+                10,
+            ),
+        ]
+    }
+
+    fn summary(groups: &[ProcessGroup]) -> Vec<(&str, usize, usize)> {
+        groups
+            .iter()
+            .map(|g| (g.name.as_str(), g.members.len(), g.threads))
+            .collect()
+    }
+
+    #[test]
+    fn groups_by_user_name_and_path() {
+        let procs = processes();
+        let users = group(&procs, GroupBy::User, None);
+        assert_eq!(
+            summary(&users),
+            [("root", 1, 0), ("www", 1, 1), ("alice", 2, 1)]
+        );
+        assert_eq!((users[2].cpu, users[2].memory), (4.5, 60));
+        let names = group(&procs, GroupBy::Name, None);
+        assert_eq!(
+            summary(&names),
+            [("nginx", 2, 1), ("bash", 1, 1), ("vim", 1, 0)]
+        );
+        let paths = group(&procs, GroupBy::Path, None);
+        assert_eq!(paths[0].name, "/usr/bin/nginx");
+        assert_eq!(paths.len(), 3);
+
+        let filter = Filter::parse("user:alice").unwrap();
+        let filtered = group(&procs, GroupBy::Name, Some(&filter));
+        assert_eq!(summary(&filtered), [("bash", 1, 1), ("vim", 1, 0)]);
+    }
+
+    #[test]
+    fn sorts_by_every_column() {
+        let procs = processes();
+        let order = |by: GroupBy, col: GroupSort, desc: bool| -> Vec<String> {
+            let mut groups = group(&procs, by, None);
+            sort_groups(&mut groups, col, desc);
+            groups.into_iter().map(|g| g.name).collect()
+        };
+        use GroupSort::*;
+        assert_eq!(order(GroupBy::User, Name, false), ["alice", "root", "www"]);
+        assert_eq!(order(GroupBy::User, Count, true), ["alice", "root", "www"]);
+        assert_eq!(
+            order(GroupBy::User, Threads, true),
+            ["www", "alice", "root"]
+        );
+        assert_eq!(order(GroupBy::User, Cpu, false), ["root", "www", "alice"]);
+        assert_eq!(
+            order(GroupBy::User, Memory, false),
+            ["alice", "root", "www"]
+        );
+        //felul intai, apoi numele
+        let kinds = order(GroupBy::Cgroup, Kind, false);
+        assert_eq!(kinds, ["4f2a9c1e8b7d", "nginx.service", "session-2.scope"]);
+    }
+}
@@ -0,0 +1,308 @@
+//predarea esantioanelor backend -> consumator (gui, tui, exporter...), in locul unui mpsc.
+//un singur loc: un ui blocat (minimizat) nu mai aduna o coada, delta-urile
+//neconsumate se comaseaza intr-unul singur (Delta::merge), cu tot cu
+//evenimentele de alerta, ca log-ul de alerte sa nu piarda nimic.
+//starea backend-ului (ultimul esantion, oprit, panica) se citeste cu health().
+use std::{
+    any::Any,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{BackendConfig, delta::Delta, run_backend, source::MetricsSource};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BackendState {
+    Running,
+    //thread-ul s-a terminat fara panica (sursa inchisa, conexiune pierduta)
+    Stopped,
+    Panicked(String),
+}
+
+impl fmt::Display for BackendState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendState::Running => write!(f, "backend running"),
+            BackendState::Stopped => write!(f, "backend stopped"),
+            BackendState::Panicked(msg) => write!(f, "backend panicked: {}", msg),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Health {
+    pub state: BackendState,
+    //None pana la primul esantion
+    pub since_last_sample: Option<Duration>,
+    //timpul dintre ultimele doua esantioane; la o gazda la distanta e
+    //intervalul agentului, nu cel din setarile locale
+    pub observed_interval: Option<Duration>,
+}
+
+impl Health {
+    //cateva intervale ratate, cu o marja pentru esantioanele lente (multe procese)
+    pub fn is_stale(&self, interval: Duration) -> bool {
+        self.since_last_sample
+            .is_some_and(|d| d > interval * 3 + Duration::from_secs(1))
+    }
+}
+
+struct Slot {
+    latest: Option<Delta>,
+    last_sample: Option<Instant>,
+    observed_interval: Option<Duration>,
+    state: BackendState,
+    receiver_alive: bool,
+}
+
+struct Shared {
+    slot: Mutex<Slot>,
+    ready: Condvar,
+}
+
+impl Shared {
+    //un thread care a facut panica tinand lock-ul nu trebuie sa blocheze si ui-ul
+    fn lock(&self) -> MutexGuard<'_, Slot> {
+        self.slot.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+pub struct SampleSender(Arc<Shared>);
+pub struct SampleReceiver(Arc<Shared>);
+
+pub fn handoff() -> (SampleSender, SampleReceiver) {
+    let shared = Arc::new(Shared {
+        slot: Mutex::new(Slot {
+            latest: None,
+            last_sample: None,
+            observed_interval: None,
+            state: BackendState::Running,
+            receiver_alive: true,
+        }),
+        ready: Condvar::new(),
+    });
+    (SampleSender(shared.clone()), SampleReceiver(shared))
+}
+
+impl SampleSender {
+    //false daca receptorul a disparut si nu mai are rost sa esantionam
+    pub fn send(&self, delta: Delta) -> bool {
+        let mut slot = self.0.lock();
+        if !slot.receiver_alive {
+            return false;
+        }
+        match &mut slot.latest {
+            Some(pending) => pending.merge(delta),
+            None => slot.latest = Some(delta),
+        }
+        let now = Instant::now();
+        slot.observed_interval = slot.last_sample.map(|t| now - t);
+        slot.last_sample = Some(now);
+        drop(slot);
+        self.0.ready.notify_all();
+        true
+    }
+
+    pub fn fail(&self, message: String) {
+        self.0.lock().state = BackendState::Panicked(message);
+        self.0.ready.notify_all();
+    }
+}
+
+impl Drop for SampleSender {
+    fn drop(&mut self) {
+        let mut slot = self.0.lock();
+        if slot.state == BackendState::Running {
+            slot.state = BackendState::Stopped;
+        }
+        drop(slot);
+        self.0.ready.notify_all();
+    }
+}
+
+impl SampleReceiver {
+    pub fn try_recv(&self) -> Option<Delta> {
+        self.0.lock().latest.take()
+    }
+
+    //blocheaza pana la un esantion nou; None cand backend-ul s-a oprit si nu mai e nimic
+    pub fn recv(&self) -> Option<Delta> {
+        let mut slot = self.0.lock();
+        loop {
+            if let Some(data) = slot.latest.take() {
+                return Some(data);
+            }
+            if slot.state != BackendState::Running {
+                return None;
+            }
+            slot = self.0.ready.wait(slot).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Delta> {
+        let deadline = Instant::now() + timeout;
+        let mut slot = self.0.lock();
+        loop {
+            if let Some(data) = slot.latest.take() {
+                return Some(data);
+            }
+            let now = Instant::now();
+            if slot.state != BackendState::Running || now >= deadline {
+                return None;
+            }
+            slot = self
+                .0
+                .ready
+                .wait_timeout(slot, deadline - now)
+                .map(|(s, _)| s)
+                .unwrap_or_else(|e| e.into_inner().0);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Delta> + '_ {
+        std::iter::from_fn(|| self.recv())
+    }
+
+    pub fn health(&self) -> Health {
+        let slot = self.0.lock();
+        Health {
+            state: slot.state.clone(),
+            since_last_sample: slot.last_sample.map(|t| t.elapsed()),
+            observed_interval: slot.observed_interval,
+        }
+    }
+}
+
+impl Drop for SampleReceiver {
+    fn drop(&mut self) {
+        self.0.lock().receiver_alive = false;
+    }
+}
+
+//porneste backend-ul pe sysinfo intr-un thread separat
+pub fn spawn_backend(
+    config: Arc<Mutex<BackendConfig>>,
+    notify: impl Fn() + Send + 'static,
+) -> SampleReceiver {
+    spawn_source(crate::source::SysinfoSource::new, config, notify)
+}
+
+//sursa se construieste pe thread-ul backend-ului; o panica ajunge in health()
+pub fn spawn_source<S: MetricsSource>(
+    make_source: impl FnOnce() -> S + Send + 'static,
+    config: Arc<Mutex<BackendConfig>>,
+    notify: impl Fn() + Send + 'static,
+) -> SampleReceiver {
+    let (tx, rx) = handoff();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            run_backend(make_source(), &tx, &notify, config)
+        }));
+        if let Err(payload) = result {
+            tx.fail(panic_message(payload));
+        }
+        drop(tx);
+        notify();
+    });
+    rx
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "backend panicked".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ProcessDetails, Stats,
+        alerts::{AlertEvent, AlertKind},
+        delta::Tick,
+        source::Sample,
+    };
+
+    fn delta(cpu: f32, alerts: &[&str]) -> Delta {
+        Delta {
+            history_len: 60,
+            stats: Stats {
+                global_cpu_usage: cpu,
+                ..Stats::default()
+            },
+            ticks: vec![Tick {
+                cpu,
+                ..Default::default()
+            }],
+            alert_events: alerts
+                .iter()
+                .map(|rule| AlertEvent {
+                    rule: rule.to_string(),
+                    kind: AlertKind::Fired,
+                    timestamp_ms: 0,
+                    message: String::new(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pending_deltas_merge_and_keep_alerts() {
+        let (tx, rx) = handoff();
+        assert!(rx.try_recv().is_none());
+        assert!(rx.health().since_last_sample.is_none());
+        assert!(tx.send(delta(1.0, &["a"])));
+        assert!(rx.health().observed_interval.is_none());
+        assert!(tx.send(delta(2.0, &[])));
+        assert!(rx.health().observed_interval.is_some());
+        assert!(tx.send(delta(3.0, &["b"])));
+        let got = rx.try_recv().unwrap();
+        assert_eq!(got.stats.global_cpu_usage, 3.0);
+        //punctele din esantioanele sarite ajung totusi in istorice
+        let cpu: Vec<f32> = got.ticks.iter().map(|t| t.cpu).collect();
+        assert_eq!(cpu, [1.0, 2.0, 3.0]);
+        let rules: Vec<&str> = got.alert_events.iter().map(|e| e.rule.as_str()).collect();
+        assert_eq!(rules, ["a", "b"]);
+        assert!(rx.try_recv().is_none());
+        assert!(rx.health().since_last_sample.is_some());
+
+        drop(tx);
+        assert_eq!(rx.health().state, BackendState::Stopped);
+        assert!(rx.recv().is_none());
+    }
+
+    #[test]
+    fn sender_sees_dropped_receiver() {
+        let (tx, rx) = handoff();
+        drop(rx);
+        assert!(!tx.send(delta(1.0, &[])));
+    }
+
+    struct Exploding;
+
+    impl MetricsSource for Exploding {
+        fn sample(&mut self) -> Sample {
+            panic!("no /proc here");
+        }
+
+        fn details(&mut self, _pid: u32) -> Option<ProcessDetails> {
+            None
+        }
+    }
+
+    #[test]
+    fn backend_panic_is_reported() {
+        let rx = spawn_source(|| Exploding, Default::default(), || {});
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_none());
+        assert_eq!(
+            rx.health().state,
+            BackendState::Panicked("no /proc here".to_string())
+        );
+    }
+}
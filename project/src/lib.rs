@@ -0,0 +1,250 @@
+//partea fara ui: modelul de date, arborele de procese si backend-ul de esantionare.
+//binarul (gui egui, tui, snapshot, agent, exporter) e in main.rs si foloseste doar ce e aici
+pub mod alerts;
+pub mod cgroup;
+pub mod delta;
+pub mod filter;
+pub mod groups;
+pub mod handoff;
+pub mod lifecycle;
+pub mod memory;
+pub mod sampler;
+pub mod source;
+pub mod tree;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use alerts::{AlertEngine, AlertEvent, AlertRule};
+use delta::Differ;
+use handoff::SampleSender;
+use serde::{Deserialize, Serialize};
+use source::MetricsSource;
+use sysinfo::ThreadKind;
+pub use tree::{ProcessTree, SubtreeTotals, build_tree, subtree_totals};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProcessData {
+    pub pid: u32,
+    pub name: String,
+    pub ppid: Option<u32>,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub path: String,
+    pub username: String,
+    pub is_thread: Option<ThreadKind>,
+    //bytes/s
+    pub disk_read: u64,
+    pub disk_write: u64,
+    //calea din /proc/<pid>/cgroup, None pentru thread-uri
+    #[serde(default)]
+    pub cgroup: Option<String>,
+    //bytes; memory de mai sus e RSS
+    #[serde(default)]
+    pub virtual_memory: u64,
+    #[serde(default)]
+    pub shared_memory: u64,
+    #[serde(default)]
+    pub swap: u64,
+    //secunde unix, 0 daca nu se stie
+    #[serde(default)]
+    pub start_time: u64,
+}
+//istoricul unui proces, start_time deosebeste un pid refolosit
+#[derive(Clone, Debug, Default)]
+pub struct ProcessHistory {
+    pub start_time: u64,
+    pub cpu: VecDeque<f32>,
+    pub memory: VecDeque<f32>,
+}
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub host_name: Option<String>,
+    pub system_name: Option<String>,
+    pub cpu_architecture: String,
+    pub os_version: Option<String>,
+    pub global_cpu_usage: f32,
+    pub kernel_long_version: String,
+    pub cores: u32,
+    pub distribution_id: String,
+    pub used_memory: u64,
+    pub total_memory: u64,
+    pub used_swap: u64,
+    pub total_swap: u64,
+    pub uptime: u64,
+    pub cpu_history: VecDeque<f32>,
+    pub core_usage: Vec<f32>,
+    pub core_history: Vec<VecDeque<f32>>,
+    pub mem_history: VecDeque<f32>,
+    pub swap_history: VecDeque<f32>,
+    pub networks: Vec<InterfaceData>,
+    pub disks: Vec<DiskData>,
+    //campurile noi vin dupa disks, ordinea din json-ul snapshot-ului e stabila
+    //din /proc/meminfo, 0 unde nu exista
+    #[serde(default)]
+    pub available_memory: u64,
+    #[serde(default)]
+    pub free_memory: u64,
+    #[serde(default)]
+    pub buffers: u64,
+    #[serde(default)]
+    pub cached: u64,
+    //secunde unix
+    #[serde(default)]
+    pub boot_time: u64,
+    //1, 5 si 15 minute
+    #[serde(default)]
+    pub load_average: [f64; 3],
+    #[serde(default)]
+    pub load_history: [VecDeque<f32>; 3],
+    //ca la top: procesele includ thread-urile de kernel, threads sunt doar cele userland
+    #[serde(default)]
+    pub process_count: usize,
+    #[serde(default)]
+    pub thread_count: usize,
+    #[serde(default)]
+    pub running: usize,
+    #[serde(default)]
+    pub sleeping: usize,
+    #[serde(default)]
+    pub zombies: usize,
+}
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskData {
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total_space: u64,
+    pub available_space: u64,
+    pub read_rate: f64,
+    pub write_rate: f64,
+}
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InterfaceData {
+    pub name: String,
+    pub mac: String,
+    pub addresses: Vec<String>,
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_history: VecDeque<f32>,
+    pub tx_history: VecDeque<f32>,
+}
+//setari citite de backend la fiecare esantion, scrise din ui
+#[derive(Clone, Debug)]
+pub struct BackendConfig {
+    pub interval: Duration,
+    pub history_len: usize,
+    pub selected_pid: Option<u32>,
+    pub alert_rules: Vec<AlertRule>,
+    //shared/swap per proces; ui-urile care nu le arata le opresc
+    pub process_memory: bool,
+}
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            history_len: 60,
+            selected_pid: None,
+            alert_rules: Vec::new(),
+            process_memory: true,
+        }
+    }
+}
+//campurile scumpe, cerute doar pentru procesul selectat
+#[derive(Clone, Debug)]
+pub struct ProcessDetails {
+    pub pid: u32,
+    pub cmd: Vec<String>,
+    pub cwd: Option<String>,
+    pub environ: Vec<String>,
+    pub status: String,
+    pub start_time: u64,
+    pub run_time: u64,
+}
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OverallData {
+    pub procese: Vec<ProcessData>,
+    pub stats: Stats,
+    #[serde(skip)]
+    pub details: Option<ProcessDetails>,
+    //evenimentele noi din esantionul asta si alertele inca active
+    #[serde(default)]
+    pub alert_events: Vec<AlertEvent>,
+    #[serde(default)]
+    pub active_alerts: Vec<AlertEvent>,
+    //procesele pornite si iesite fata de esantionul anterior
+    #[serde(default)]
+    pub process_events: Vec<lifecycle::ProcessEvent>,
+    #[serde(skip)]
+    pub process_history: HashMap<u32, ProcessHistory>,
+}
+
+pub fn bytes_to_mb(bytes: u64) -> f64 {
+    bytes as f64 / 1048576.0
+}
+pub fn bytes_to_gb(bytes: u64) -> f64 {
+    bytes as f64 / 1073741824.0
+}
+
+//bucla de esantionare, pornita de handoff::spawn_backend pe un thread separat.
+//notify e apelat dupa fiecare esantion (request_repaint in gui, nimic in tui)
+//prin handoff pleaca doar diferenta fata de esantionul anterior (delta.rs)
+pub fn run_backend(
+    mut source: impl MetricsSource,
+    tx: &SampleSender,
+    notify: impl Fn(),
+    config: Arc<Mutex<BackendConfig>>,
+) {
+    let mut differ = Differ::new();
+    let mut last_tick: Option<Instant> = None;
+    let mut alerts = AlertEngine::new(read_config(&config, |c| c.alert_rules.clone()));
+    loop {
+        let (history_len, selected_pid, process_memory) = read_config(&config, |c| {
+            (c.history_len, c.selected_pid, c.process_memory)
+        });
+        //primul esantion contine totalurile de la pornire, nu o rata
+        let elapsed = last_tick.map(|t| t.elapsed().as_secs_f64());
+        let tick_start = Instant::now();
+        last_tick = Some(tick_start);
+
+        source.set_process_memory(process_memory);
+        let mut data = sampler::assemble(source.sample(), elapsed);
+        data.details = selected_pid.and_then(|pid| source.details(pid));
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        data.alert_events = alerts.evaluate(now_ms, &data);
+        data.active_alerts = alerts.active();
+
+        if !tx.send(differ.diff(data, history_len, now_ms)) {
+            break;
+        }
+        notify();
+        //dormim in pasi mici ca un interval nou sa se aplice imediat
+        loop {
+            let interval = read_config(&config, |c| c.interval);
+            let passed = tick_start.elapsed();
+            if passed >= interval {
+                break;
+            }
+            thread::sleep((interval - passed).min(Duration::from_millis(100)));
+        }
+    }
+}
+
+//doar campurile cerute, fara sa clonam lista de reguli la fiecare 100ms
+fn read_config<T>(config: &Mutex<BackendConfig>, f: impl FnOnce(&BackendConfig) -> T) -> T {
+    match config.lock() {
+        Ok(c) => f(&c),
+        Err(poisoned) => f(&poisoned.into_inner()),
+    }
+}
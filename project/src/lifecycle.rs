@@ -0,0 +1,57 @@
+//pornirile si iesirile de procese. backend-ul (delta::Differ) compara doua esantioane
+//consecutive dupa (pid, start_time): un pid refolosit inseamna o iesire si o pornire.
+//consumatorul le strange intr-un log marginit (delta::Mirror::events)
+use serde::{Deserialize, Serialize};
+use sysinfo::ThreadKind;
+
+use crate::{ProcessData, filter::Filter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LifecycleKind {
+    Started,
+    Exited,
+}
+
+impl LifecycleKind {
+    //filtrele din view-ul de evenimente, None = toate
+    pub const FILTERS: [Option<LifecycleKind>; 3] = [
+        None,
+        Some(LifecycleKind::Started),
+        Some(LifecycleKind::Exited),
+    ];
+
+    pub fn filter_label(kind: Option<LifecycleKind>) -> &'static str {
+        match kind {
+            None => "All events",
+            Some(LifecycleKind::Started) => "Started",
+            Some(LifecycleKind::Exited) => "Exited",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProcessEvent {
+    pub kind: LifecycleKind,
+    pub timestamp_ms: u64,
+    //la pornire primul esantion, la iesire ultimul vazut (cpu si memorie finale)
+    pub process: ProcessData,
+}
+
+impl ProcessEvent {
+    pub fn new(kind: LifecycleKind, timestamp_ms: u64, process: &ProcessData) -> Self {
+        Self {
+            kind,
+            timestamp_ms,
+            process: process.clone(),
+        }
+    }
+
+    //thread-urile userland apar si dispar prea des, ar ineca log-ul
+    pub fn tracked(p: &ProcessData) -> bool {
+        p.is_thread != Some(ThreadKind::Userland)
+    }
+
+    pub fn matches(&self, kind: Option<LifecycleKind>, filter: Option<&Filter>) -> bool {
+        kind.is_none_or(|k| k == self.kind) && filter.is_none_or(|f| f.matches(&self.process))
+    }
+}
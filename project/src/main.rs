@@ -1,6 +1,12 @@
+mod metrics;
+mod recording;
+mod remote;
+mod snapshot;
+mod tui;
+
 use std::{
-    collections::{HashMap, VecDeque},
-    sync::mpsc::{Receiver, Sender, channel},
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex},
     thread,
     time::Duration,
 };
@@ -8,167 +14,753 @@ use std::{
 use eframe::egui;
 use eframe::epaint::{Color32, PathShape, Pos2, Stroke};
 use egui_extras::{Column, TableBuilder};
-use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, ThreadKind, Users};
-#[derive(PartialEq)]
+use project::{
+    BackendConfig, DiskData, InterfaceData, OverallData, ProcessData, Stats, SubtreeTotals,
+    alerts::{self, AlertEvent, AlertKind},
+    bytes_to_gb, bytes_to_mb,
+    delta::{self, Delta, Mirror},
+    filter::Filter,
+    groups::{self, GroupBy, GroupSort},
+    handoff::{self, BackendState, SampleReceiver},
+    lifecycle::{LifecycleKind, ProcessEvent},
+    subtree_totals,
+};
+use recording::Replay;
+use remote::HostState;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
 enum ViewMode {
     List = 0,
     Tree = 1,
     Overview = 2,
+    Groups = 3,
+    Events = 4,
 }
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 enum SortColumn {
     Pid,
     Name,
     Cpu,
     Memory,
+    DiskRead,
+    DiskWrite,
+    VirtualMemory,
+    SharedMemory,
+    Swap,
+}
+//coloanele de memorie din lista care se pot ascunde
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+struct MemColumns {
+    rss: bool,
+    virtual_memory: bool,
+    shared: bool,
+    swap: bool,
+}
+impl MemColumns {
+    //coloanele optionale de dupa RSS, in ordinea din tabel
+    fn extra(self) -> Vec<(&'static str, SortColumn)> {
+        [
+            (self.virtual_memory, "Virt", SortColumn::VirtualMemory),
+            (self.shared, "Shared", SortColumn::SharedMemory),
+            (self.swap, "Swap", SortColumn::Swap),
+        ]
+        .into_iter()
+        .filter(|c| c.0)
+        .map(|(_, label, col)| (label, col))
+        .collect()
+    }
+
+    fn shows(self, col: SortColumn) -> bool {
+        match col {
+            SortColumn::Memory => self.rss,
+            SortColumn::VirtualMemory => self.virtual_memory,
+            SortColumn::SharedMemory => self.shared,
+            SortColumn::Swap => self.swap,
+            _ => true,
+        }
+    }
+}
+impl Default for MemColumns {
+    fn default() -> Self {
+        Self {
+            rss: true,
+            virtual_memory: false,
+            shared: false,
+            swap: false,
+        }
+    }
+}
+//coloanele dupa care se sorteaza fratii in tree
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+enum TreeSort {
+    Pid,
+    Name,
+    Cpu,
+    Memory,
+    User,
+    Path,
+    SubtreeCpu,
+    SubtreeMemory,
+    Descendants,
 }
+//ce se salveaza intre rulari prin storage-ul eframe
+//latimile coloanelor si marimea ferestrei le persista egui/eframe singure
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct UiSettings {
+    view: ViewMode,
+    sort_col: SortColumn,
+    sort_desc: bool,
+    mem_columns: MemColumns,
+    tree_sort: TreeSort,
+    tree_sort_desc: bool,
+    group_by: GroupBy,
+    group_sort: GroupSort,
+    group_sort_desc: bool,
+    show_threads: bool,
+    show_cpu_graph: bool,
+    show_mem_graph: bool,
+    show_swap_graph: bool,
+    show_core_heatmap: bool,
+    show_net_graphs: HashSet<String>,
+}
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self {
+            view: ViewMode::Overview,
+            sort_col: SortColumn::Cpu,
+            sort_desc: true,
+            mem_columns: MemColumns::default(),
+            tree_sort: TreeSort::SubtreeCpu,
+            tree_sort_desc: true,
+            group_by: GroupBy::Cgroup,
+            group_sort: GroupSort::Cpu,
+            group_sort_desc: true,
+            show_threads: false,
+            show_cpu_graph: false,
+            show_mem_graph: false,
+            show_swap_graph: false,
+            show_core_heatmap: false,
+            show_net_graphs: HashSet::new(),
+        }
+    }
+}
+const SETTINGS_KEY: &str = "ui_settings";
+//tabelele cu latimi de coloane salvate, uitate la reset settings
+const TABLES: [&str; 4] = [
+    "process_list",
+    "process_tree",
+    "process_events",
+    "process_groups",
+];
+const DEFAULT_WINDOW_SIZE: [f32; 2] = [750.0, 750.0];
+const ALERT_LOG_LEN: usize = 500;
+const SPARKLINE_WIDTH: f32 = 70.0;
+const CPU_COLOR: Color32 = Color32::from_rgb(0, 255, 255); //turcoaz
+const MEM_COLOR: Color32 = Color32::from_rgb(255, 0, 255); //mov
+const NEW_COLOR: Color32 = Color32::from_rgb(0, 200, 120); //verde
+//cat ramane evidentiat un proces pornit de curand (gui si tui)
+const NEW_HIGHLIGHT: Duration = Duration::from_secs(3);
+const MIN_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_INTERVAL: Duration = Duration::from_secs(60);
+const MIN_HISTORY: usize = 10;
+const MAX_HISTORY: usize = 3600;
 #[derive(Clone, Debug)]
-struct ProcessData {
+struct SignalRequest {
     pid: u32,
     name: String,
-    ppid: Option<u32>,
-    cpu_usage: f32,
-    memory: u64,
-    path: String,
-    username: String,
-    is_thread: Option<ThreadKind>,
+    signal: Signal,
 }
-#[derive(Clone, Debug)]
-struct Stats {
-    host_name: Option<String>,
-    system_name: Option<String>,
-    cpu_architecture: String,
-    os_version: Option<String>,
-    global_cpu_usage: f32,
-    kernel_long_version: String,
-    cores: u32,
-    distribution_id: String,
-    used_memory: u64,
-    total_memory: u64,
-    used_swap: u64,
-    total_swap: u64,
-    uptime: u64,
-    cpu_history: VecDeque<f32>,
-    mem_history: VecDeque<f32>,
-    swap_history: VecDeque<f32>,
-}
-#[derive(Clone, Debug)]
-struct OverallData {
-    procese: Vec<ProcessData>,
-    stats: Stats,
+
+//sursa de esantioane: backend-ul local (state None) sau un agent la distanta
+struct Host {
+    name: String,
+    rx: SampleReceiver,
+    state: Option<Arc<Mutex<HostState>>>,
+    //starea gazdei cat timp nu e cea afisata
+    mirror: Mirror,
 }
 
 struct TaskManager {
-    rx: Receiver<OverallData>,
+    hosts: Vec<Host>,
+    host: usize,
     //ui type shit
     view: ViewMode,
-    cur_data: Option<OverallData>,
+    //starea gazdei afisate, tinuta la zi din delta-uri
+    mirror: Mirror,
     show_threads: bool,
 
+    //filtru
+    filter_text: String,
+    filter: Result<Filter, String>,
+    filter_visible: HashSet<u32>,
+
     //tree ui
-    radacini: Vec<u32>,
-    tree_cache: HashMap<u32, Vec<u32>>,
-    process_map: HashMap<u32, ProcessData>,
+    subtree: HashMap<u32, SubtreeTotals>,
+    tree_expanded: HashSet<u32>,
+    tree_sort: TreeSort,
+    tree_sort_desc: bool,
+
+    //grupuri
+    group_expanded: HashSet<String>,
+    group_by: GroupBy,
+    group_sort: GroupSort,
+    group_sort_desc: bool,
 
     //statisticile
     show_cpu_graph: bool,
     show_mem_graph: bool,
     show_swap_graph: bool,
+    show_net_graphs: HashSet<String>,
+    show_core_heatmap: bool,
 
     //sortare list
     sort_col: SortColumn,
     sort_desc: bool,
+    mem_columns: MemColumns,
+
+    //detalii proces
+    selected_pid: Option<u32>,
+
+    //setari backend + pauza
+    backend_config: Arc<Mutex<BackendConfig>>,
+    show_settings: bool,
+    paused: bool,
+    //delta-urile primite in pauza, comasate
+    paused_delta: Option<Delta>,
+
+    //semnale
+    pending_signal: Option<SignalRequest>,
+    signal_status: Option<Result<String, String>>,
+
+    //redare din fisier in loc de backend
+    replay: Option<Replay>,
+    replay_error: Option<String>,
+
+    //alerte
+    alert_log: VecDeque<AlertEvent>,
+    show_alerts: bool,
+
+    //evenimente de procese, None = toate
+    event_kind: Option<LifecycleKind>,
+
+    //tabelele ale caror latimi se uita la urmatoarea desenare (reset settings)
+    reset_tables: HashSet<&'static str>,
 }
 
 impl TaskManager {
-    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(
+        cc: &eframe::CreationContext<'_>,
+        config: BackendConfig,
+        replay: Option<Replay>,
+        remotes: Vec<String>,
+    ) -> Self {
         let ctx = cc.egui_ctx.clone();
         ctx.set_visuals(egui::Visuals::dark());
 
-        let (tx, rx) = channel();
-        thread::spawn(move || {
-            backend(tx, ctx);
-        });
-
-        Self {
+        let backend_config = Arc::new(Mutex::new(config));
+        let rx = if replay.is_none() {
+            let ctx = ctx.clone();
+            handoff::spawn_backend(backend_config.clone(), move || ctx.request_repaint())
+        } else {
+            //la replay nu ruleaza niciun backend, receptorul ramane gol
+            handoff::handoff().1
+        };
+        let mut hosts = vec![Host {
+            name: "local".to_string(),
             rx,
+            state: None,
+            mirror: Mirror::new(),
+        }];
+        for addr in remotes {
+            let (tx, rx) = handoff::handoff();
+            let state = Arc::new(Mutex::new(HostState::Connecting));
+            let shared = state.clone();
+            let ctx = ctx.clone();
+            let target = addr.clone();
+            thread::spawn(move || {
+                remote::connect(target, tx, move || ctx.request_repaint(), shared);
+            });
+            hosts.push(Host {
+                name: addr,
+                rx,
+                state: Some(state),
+                mirror: Mirror::new(),
+            });
+        }
+
+        let mut app = Self {
+            hosts,
+            host: 0,
             view: ViewMode::Overview,
-            cur_data: None,
+            mirror: Mirror::new(),
             show_threads: false,
-            radacini: Vec::new(),
-            tree_cache: HashMap::new(),
-            process_map: HashMap::new(),
+            filter_text: String::new(),
+            filter: Ok(Filter::default()),
+            filter_visible: HashSet::new(),
+            subtree: HashMap::new(),
+            tree_expanded: HashSet::new(),
+            tree_sort: TreeSort::SubtreeCpu,
+            tree_sort_desc: true,
+            group_expanded: HashSet::new(),
+            group_by: GroupBy::Cgroup,
+            group_sort: GroupSort::Cpu,
+            group_sort_desc: true,
             show_cpu_graph: false,
             show_mem_graph: false,
             show_swap_graph: false,
+            show_net_graphs: HashSet::new(),
+            show_core_heatmap: false,
             sort_col: SortColumn::Cpu,
             sort_desc: true,
+            mem_columns: MemColumns::default(),
+            selected_pid: None,
+            backend_config,
+            show_settings: false,
+            paused: false,
+            paused_delta: None,
+            reset_tables: HashSet::new(),
+            pending_signal: None,
+            signal_status: None,
+            replay,
+            replay_error: None,
+            alert_log: VecDeque::new(),
+            show_alerts: false,
+            event_kind: None,
+        };
+        let settings = cc
+            .storage
+            .and_then(|s| eframe::get_value::<UiSettings>(s, SETTINGS_KEY))
+            .unwrap_or_default();
+        app.apply_settings(settings);
+        app.load_replay_frame();
+        app
+    }
+
+    fn settings(&self) -> UiSettings {
+        UiSettings {
+            view: self.view,
+            sort_col: self.sort_col,
+            sort_desc: self.sort_desc,
+            mem_columns: self.mem_columns,
+            tree_sort: self.tree_sort,
+            tree_sort_desc: self.tree_sort_desc,
+            group_by: self.group_by,
+            group_sort: self.group_sort,
+            group_sort_desc: self.group_sort_desc,
+            show_threads: self.show_threads,
+            show_cpu_graph: self.show_cpu_graph,
+            show_mem_graph: self.show_mem_graph,
+            show_swap_graph: self.show_swap_graph,
+            show_core_heatmap: self.show_core_heatmap,
+            show_net_graphs: self.show_net_graphs.clone(),
+        }
+    }
+
+    fn apply_settings(&mut self, s: UiSettings) {
+        self.view = s.view;
+        self.sort_col = s.sort_col;
+        self.sort_desc = s.sort_desc;
+        self.mem_columns = s.mem_columns;
+        self.tree_sort = s.tree_sort;
+        self.tree_sort_desc = s.tree_sort_desc;
+        self.group_by = s.group_by;
+        self.group_sort = s.group_sort;
+        self.group_sort_desc = s.group_sort_desc;
+        self.show_threads = s.show_threads;
+        self.show_cpu_graph = s.show_cpu_graph;
+        self.show_mem_graph = s.show_mem_graph;
+        self.show_swap_graph = s.show_swap_graph;
+        self.show_core_heatmap = s.show_core_heatmap;
+        self.show_net_graphs = s.show_net_graphs;
+    }
+
+    fn reset_settings(&mut self, ctx: &egui::Context) {
+        self.apply_settings(UiSettings::default());
+        //uita doar latimile coloanelor, nu si tema, zoom-ul sau focusul din egui
+        self.reset_tables = TABLES.into_iter().collect();
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(DEFAULT_WINDOW_SIZE.into()));
+    }
+
+    fn load_replay_frame(&mut self) {
+        let history_len = self.history_len();
+        let Some(replay) = self.replay.as_mut() else {
+            return;
+        };
+        match replay.load(replay.pos, history_len) {
+            Ok(data) => {
+                self.replay_error = None;
+                self.mirror.replace(data);
+                self.refresh_view();
+            }
+            Err(e) => self.replay_error = Some(e.to_string()),
+        }
+    }
+
+    fn render_replay_bar(&mut self, ctx: &egui::Context) {
+        let Some(replay) = self.replay.as_mut() else {
+            return;
+        };
+        let mut changed = replay.tick();
+        egui::TopBottomPanel::bottom("replay_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let icon = if replay.playing { "⏸" } else { "▶" };
+                if ui.button(icon).clicked() {
+                    replay.set_playing(!replay.playing);
+                }
+
+                egui::ComboBox::from_id_salt("replay_speed")
+                    .width(60.0)
+                    .selected_text(format!("{}x", replay.speed))
+                    .show_ui(ui, |ui| {
+                        for speed in [0.5, 1.0, 2.0, 5.0, 10.0, 60.0] {
+                            ui.selectable_value(&mut replay.speed, speed, format!("{}x", speed));
+                        }
+                    });
+
+                ui.label(format_timestamp(replay.timestamp_ms(replay.pos) / 1000));
+                ui.label(
+                    egui::RichText::new(format!("{}/{}", replay.pos + 1, replay.len())).weak(),
+                );
+
+                let mut pos = replay.pos;
+                ui.spacing_mut().slider_width = ui.available_width() - 10.0;
+                let slider =
+                    ui.add(egui::Slider::new(&mut pos, 0..=replay.len() - 1).show_value(false));
+                if slider.changed() {
+                    replay.seek(pos);
+                    changed = true;
+                }
+            });
+            if let Some(e) = &self.replay_error {
+                ui.label(egui::RichText::new(e).color(Color32::LIGHT_RED));
+            }
+        });
+        if replay.playing {
+            ctx.request_repaint_after(Duration::from_millis(50));
         }
+        if changed {
+            self.load_replay_frame();
+        }
+    }
+
+    //dupa orice schimbare a datelor afisate; harta si arborele sunt deja la zi in mirror
+    fn refresh_view(&mut self) {
+        let mirror = &self.mirror;
+        self.subtree = subtree_totals(mirror.tree(), |pid| mirror.get(pid));
+        self.apply_filter();
     }
 
-    fn prepare_data(&mut self, data: OverallData) {
-        self.process_map.clear();
-        self.process_map.reserve(data.procese.len());
-        for p in &data.procese {
-            self.process_map.insert(p.pid, p.clone());
+    fn select(&mut self, pid: Option<u32>) {
+        self.selected_pid = pid;
+        if let Ok(mut cfg) = self.backend_config.lock() {
+            cfg.selected_pid = pid;
         }
-        self.tree_cache.clear();
-        self.radacini.clear();
-        for p in &data.procese {
-            if let Some(ppid) = p.ppid {
-                if self.process_map.contains_key(&ppid) {
-                    self.tree_cache.entry(ppid).or_default().push(p.pid);
+    }
+
+    fn history_len(&self) -> usize {
+        self.backend_config
+            .lock()
+            .map(|c| c.history_len)
+            .unwrap_or_default()
+    }
+
+    //semnalele si detaliile merg doar pe procesele de pe masina asta
+    fn is_local_live(&self) -> bool {
+        self.replay.is_none() && self.hosts[self.host].state.is_none()
+    }
+
+    fn poll_hosts(&mut self) {
+        let mut fresh = None;
+        let mut events = Vec::new();
+        for (i, host) in self.hosts.iter_mut().enumerate() {
+            //un singur delta; cele sarite sunt deja comasate in el
+            if let Some(delta) = host.rx.try_recv() {
+                if i == self.host {
+                    //alertele intra in log si in pauza, altfel s-ar pierde
+                    events.extend(delta.alert_events.iter().cloned());
+                    fresh = Some(delta);
                 } else {
-                    //parinte fantoma
-                    self.radacini.push(p.pid);
+                    host.mirror.apply(delta);
+                }
+            }
+        }
+        self.log_alerts(&events);
+        if let Some(delta) = fresh {
+            //in pauza backend-ul continua, delta-urile se strang pana la reluare
+            if self.paused {
+                match &mut self.paused_delta {
+                    Some(pending) => pending.merge(delta),
+                    None => self.paused_delta = Some(delta),
                 }
             } else {
-                self.radacini.push(p.pid);
+                self.mirror.apply(delta);
+                self.refresh_view();
             }
         }
-        self.cur_data = Some(data);
     }
-    fn render_list(&mut self, ui: &mut egui::Ui) {
-        let data = if let Some(d) = &self.cur_data {
-            d
+
+    fn switch_host(&mut self, host: usize) {
+        if host == self.host {
+            return;
+        }
+        //gazda veche isi pastreaza starea ca sa revenim instant
+        if let Some(delta) = self.paused_delta.take() {
+            self.mirror.apply(delta);
+        }
+        std::mem::swap(&mut self.mirror, &mut self.hosts[self.host].mirror);
+        self.host = host;
+        self.select(None);
+        std::mem::swap(&mut self.mirror, &mut self.hosts[host].mirror);
+        self.refresh_view();
+    }
+
+    fn render_host_selector(&mut self, ui: &mut egui::Ui) {
+        let status = |h: &Host| match h.state.as_ref().and_then(|s| s.lock().ok()) {
+            None => None,
+            Some(s) => match &*s {
+                HostState::Connected => None,
+                HostState::Connecting => Some(("connecting…".to_string(), Color32::YELLOW)),
+                HostState::Failed(e) => Some((e.clone(), Color32::LIGHT_RED)),
+            },
+        };
+        let mut chosen = self.host;
+        egui::ComboBox::from_id_salt("host")
+            .selected_text(&self.hosts[self.host].name)
+            .show_ui(ui, |ui| {
+                for (i, h) in self.hosts.iter().enumerate() {
+                    let mut label = egui::RichText::new(&h.name);
+                    if let Some((_, color)) = status(h) {
+                        label = label.color(color);
+                    }
+                    ui.selectable_value(&mut chosen, i, label);
+                }
+            });
+        if let Some((text, color)) = status(&self.hosts[self.host]) {
+            ui.label(egui::RichText::new(text).color(color));
+        }
+        self.switch_host(chosen);
+    }
+
+    //cat de proaspete sunt datele gazdei afisate; se redeseneaza singur ca sa creasca timpul
+    fn render_health(&self, ui: &mut egui::Ui) {
+        let host = &self.hosts[self.host];
+        let health = host.rx.health();
+        //un agent poate esantiona mai rar decat setarea locala; pana la al doilea
+        //cadru nu stim cat de rar
+        let interval = if host.state.is_some() {
+            health.observed_interval
         } else {
+            self.backend_config.lock().map(|c| c.interval).ok()
+        };
+        let stale = interval.is_some_and(|i| health.is_stale(i));
+        let (text, color) = match (&health.state, health.since_last_sample) {
+            (BackendState::Running, None) => ("● waiting".to_string(), Color32::GRAY),
+            (BackendState::Running, Some(d)) if stale => (
+                format!("● stale, {} ago", format_duration(d.as_secs())),
+                Color32::YELLOW,
+            ),
+            (BackendState::Running, Some(d)) => (
+                format!("● {:.1}s ago", d.as_secs_f32()),
+                Color32::from_rgb(0, 200, 120),
+            ),
+            (state, _) => (format!("✖ {}", state), Color32::LIGHT_RED),
+        };
+        ui.label(egui::RichText::new(text).color(color))
+            .on_hover_text("Time since the last sample from the backend");
+        if health.state == BackendState::Running {
+            ui.ctx().request_repaint_after(Duration::from_secs(1));
+        }
+    }
+
+    //distanta dintre doua puncte din grafice; la o gazda la distanta e ritmul agentului
+    fn sample_interval(&self) -> Duration {
+        let local = self
+            .backend_config
+            .lock()
+            .map(|c| c.interval)
+            .unwrap_or_default();
+        let host = &self.hosts[self.host];
+        match host.state {
+            Some(_) => host.rx.health().observed_interval.unwrap_or(local),
+            None => local,
+        }
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        if !paused && let Some(delta) = self.paused_delta.take() {
+            self.mirror.apply(delta);
+            self.refresh_view();
+        }
+    }
+
+    fn render_settings(&mut self, ctx: &egui::Context) {
+        if !self.show_settings {
+            return;
+        }
+        let mut open = true;
+        let mut cfg = self
+            .backend_config
+            .lock()
+            .map(|c| c.clone())
+            .unwrap_or_default();
+        let mut secs = cfg.interval.as_secs_f32();
+        let mut changed = false;
+        let mut reset = false;
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("settings_grid")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("Refresh interval");
+                        changed |= ui
+                            .add(
+                                egui::Slider::new(
+                                    &mut secs,
+                                    MIN_INTERVAL.as_secs_f32()..=MAX_INTERVAL.as_secs_f32(),
+                                )
+                                .logarithmic(true)
+                                .suffix(" s"),
+                            )
+                            .changed();
+                        ui.end_row();
+                        ui.label("History length");
+                        changed |= ui
+                            .add(
+                                egui::Slider::new(&mut cfg.history_len, MIN_HISTORY..=MAX_HISTORY)
+                                    .logarithmic(true)
+                                    .suffix(" samples"),
+                            )
+                            .changed();
+                        ui.end_row();
+                    });
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Graphs cover about {}",
+                        format_duration((secs * cfg.history_len as f32) as u64)
+                    ))
+                    .weak(),
+                );
+                if self.hosts.len() > 1 {
+                    ui.label(
+                        egui::RichText::new("Interval and history apply to the local backend")
+                            .weak(),
+                    );
+                }
+                ui.separator();
+                if ui
+                    .button("Reset layout to defaults")
+                    .on_hover_text("View, sorting, toggles, column widths and window size")
+                    .clicked()
+                {
+                    reset = true;
+                }
+            });
+        if reset {
+            self.reset_settings(ctx);
+        }
+        if changed {
+            cfg.interval = Duration::from_secs_f32(secs);
+            if let Ok(mut shared) = self.backend_config.lock() {
+                shared.interval = cfg.interval;
+                shared.history_len = cfg.history_len;
+            }
+        }
+        self.show_settings = open;
+    }
+
+    fn active_filter(&self) -> Option<&Filter> {
+        match &self.filter {
+            Ok(f) if !f.is_empty() => Some(f),
+            _ => None,
+        }
+    }
+
+    //in tree raman vizibili si stramosii proceselor care se potrivesc
+    fn apply_filter(&mut self) {
+        self.filter_visible.clear();
+        let Some(filter) = self.active_filter() else {
             return;
         };
-        let processes = &data.procese;
+        let mut visible = HashSet::new();
+        let processes = self.mirror.data().map(|d| d.procese.as_slice());
+        for p in processes
+            .unwrap_or_default()
+            .iter()
+            .filter(|p| filter.matches(p))
+        {
+            let mut cur = Some(p.pid);
+            while let Some(pid) = cur {
+                if !visible.insert(pid) {
+                    break;
+                }
+                cur = self.mirror.get(pid).and_then(|p| p.ppid);
+            }
+        }
+        self.filter_visible = visible;
+    }
 
+    fn render_filter_bar(&mut self, ui: &mut egui::Ui) {
+        ui.label("Filter:");
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.filter_text)
+                .hint_text("name~firefox user:root cpu>5 mem>200MB path:/usr/bin")
+                .desired_width(320.0),
+        );
+        if response.changed() {
+            self.filter = Filter::parse(&self.filter_text);
+            self.apply_filter();
+        }
+        if let Err(e) = &self.filter {
+            ui.label(egui::RichText::new(e).color(Color32::LIGHT_RED));
+        }
+    }
+    fn render_list(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             ui.checkbox(&mut self.show_threads, "Show Threads");
+            ui.menu_button("Columns", |ui| {
+                let cols = &mut self.mem_columns;
+                ui.checkbox(&mut cols.rss, "RSS");
+                ui.checkbox(&mut cols.virtual_memory, "Virtual size");
+                ui.checkbox(&mut cols.shared, "Shared");
+                ui.checkbox(&mut cols.swap, "Swap");
+            });
+            ui.separator();
+            self.render_filter_bar(ui);
         });
+        //sortarea dupa o coloana ascunsa n-ar mai avea cum fi vazuta sau schimbata
+        if !self.mem_columns.shows(self.sort_col) {
+            self.sort_col = SortColumn::Cpu;
+            self.sort_desc = true;
+        }
+
+        let data = if let Some(d) = self.mirror.data() {
+            d
+        } else {
+            return;
+        };
+        let processes = &data.procese;
+        let filter = self.active_filter();
 
         //mapez proces la indice, sortez numai indicii pt eficienta
         let mut indices: Vec<usize> = processes
             .iter()
             .enumerate()
             .filter(|(_, p)| self.show_threads || p.is_thread.is_none())
+            .filter(|(_, p)| filter.is_none_or(|f| f.matches(p)))
             .map(|(i, _)| i)
             .collect();
 
         let sort_col = self.sort_col;
         let sort_desc = self.sort_desc;
 
-        match sort_col {
-            SortColumn::Pid => indices.sort_by(|&a, &b| {
-                let cmp = processes[a].pid.cmp(&processes[b].pid);
-                if sort_desc { cmp.reverse() } else { cmp }
-            }),
-            SortColumn::Name => indices.sort_by(|&a, &b| {
-                let cmp = processes[a].name.cmp(&processes[b].name);
-                if sort_desc { cmp.reverse() } else { cmp }
-            }),
-            SortColumn::Cpu => indices.sort_by(|&a, &b| {
-                let cmp = processes[a].cpu_usage.total_cmp(&processes[b].cpu_usage);
-                if sort_desc { cmp.reverse() } else { cmp }
-            }),
-            SortColumn::Memory => indices.sort_by(|&a, &b| {
-                let cmp = processes[a].memory.cmp(&processes[b].memory);
-                if sort_desc { cmp.reverse() } else { cmp }
-            }),
-        }
+        sort_indices(processes, &mut indices, sort_col, sort_desc);
 
         let text_height = egui::TextStyle::Body.resolve(ui.style()).size + 2.0;
 
@@ -198,13 +790,35 @@ impl TaskManager {
             }
         };
 
-        TableBuilder::new(ui)
+        let mut requested: Option<SignalRequest> = None;
+        let mut clicked: Option<u32> = None;
+        let selected_pid = self.selected_pid;
+        let live = self.is_local_live();
+        let history_len = self.history_len();
+        let cols = self.mem_columns;
+        let mem_cols = cols.extra();
+
+        let mut table = table_builder(ui, "process_list", self.reset_tables.remove("process_list"))
             .striped(true)
             .resizable(true)
+            .sense(egui::Sense::click())
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
             .column(Column::auto().at_least(60.0))
             .column(Column::initial(150.0))
             .column(Column::auto().at_least(60.0))
+            .column(Column::exact(SPARKLINE_WIDTH));
+        //graficul de memorie merge impreuna cu RSS
+        if cols.rss {
+            table = table
+                .column(Column::auto().at_least(80.0))
+                .column(Column::exact(SPARKLINE_WIDTH));
+        }
+        for _ in &mem_cols {
+            table = table.column(Column::auto().at_least(80.0));
+        }
+        table
+            .column(Column::auto().at_least(80.0))
+            .column(Column::auto().at_least(80.0))
             .column(Column::auto().at_least(80.0))
             .column(Column::initial(80.0))
             .column(Column::remainder())
@@ -236,11 +850,42 @@ impl TaskManager {
                         &mut next_sort_desc,
                     )
                 });
+                header.col(|ui| {
+                    ui.strong("CPU trend");
+                });
+                if cols.rss {
+                    header.col(|ui| {
+                        header_btn(
+                            ui,
+                            "RSS",
+                            SortColumn::Memory,
+                            &mut next_sort_col,
+                            &mut next_sort_desc,
+                        )
+                    });
+                    header.col(|ui| {
+                        ui.strong("Mem trend");
+                    });
+                }
+                for &(label, col) in &mem_cols {
+                    header.col(|ui| {
+                        header_btn(ui, label, col, &mut next_sort_col, &mut next_sort_desc)
+                    });
+                }
+                header.col(|ui| {
+                    header_btn(
+                        ui,
+                        "Read",
+                        SortColumn::DiskRead,
+                        &mut next_sort_col,
+                        &mut next_sort_desc,
+                    )
+                });
                 header.col(|ui| {
                     header_btn(
                         ui,
-                        "Mem",
-                        SortColumn::Memory,
+                        "Write",
+                        SortColumn::DiskWrite,
                         &mut next_sort_col,
                         &mut next_sort_desc,
                     )
@@ -255,17 +900,57 @@ impl TaskManager {
             .body(|body| {
                 body.rows(text_height, indices.len(), |mut row| {
                     let p = &processes[indices[row.index()]];
+                    row.set_selected(selected_pid == Some(p.pid));
+                    let text = |s: String| {
+                        let t = egui::RichText::new(s);
+                        if self.is_new(p.pid) {
+                            t.color(NEW_COLOR).strong()
+                        } else {
+                            t
+                        }
+                    };
                     row.col(|ui| {
-                        ui.label(p.pid.to_string());
+                        ui.label(text(p.pid.to_string()));
                     });
                     row.col(|ui| {
-                        ui.label(&p.name);
+                        ui.label(text(p.name.clone()));
                     });
+                    let history = data.process_history.get(&p.pid);
                     row.col(|ui| {
                         ui.label(format!("{:.1}", p.cpu_usage));
                     });
                     row.col(|ui| {
-                        ui.label(format!("{:.2} MB", bytes_to_mb(p.memory)));
+                        if let Some(h) = history {
+                            let size = egui::vec2(SPARKLINE_WIDTH, text_height - 4.0);
+                            draw_sparkline(ui, size, &h.cpu, history_len, 1.0, CPU_COLOR);
+                        }
+                    });
+                    if cols.rss {
+                        row.col(|ui| {
+                            ui.label(format!("{:.2} MB", bytes_to_mb(p.memory)));
+                        });
+                        row.col(|ui| {
+                            if let Some(h) = history {
+                                let size = egui::vec2(SPARKLINE_WIDTH, text_height - 4.0);
+                                draw_sparkline(ui, size, &h.memory, history_len, 0.0, MEM_COLOR);
+                            }
+                        });
+                    }
+                    for &(_, col) in &mem_cols {
+                        row.col(|ui| {
+                            let bytes = match col {
+                                SortColumn::VirtualMemory => p.virtual_memory,
+                                SortColumn::SharedMemory => p.shared_memory,
+                                _ => p.swap,
+                            };
+                            ui.label(format!("{:.2} MB", bytes_to_mb(bytes)));
+                        });
+                    }
+                    row.col(|ui| {
+                        ui.label(format_rate(p.disk_read as f64));
+                    });
+                    row.col(|ui| {
+                        ui.label(format_rate(p.disk_write as f64));
                     });
                     row.col(|ui| {
                         ui.label(&p.username);
@@ -273,46 +958,862 @@ impl TaskManager {
                     row.col(|ui| {
                         ui.label(&p.path);
                     });
+                    let response = row.response();
+                    if response.clicked() {
+                        clicked = Some(p.pid);
+                    }
+                    //in replay pid-urile nu mai corespund proceselor reale
+                    if live {
+                        response.context_menu(|ui| {
+                            if let Some(req) = signal_menu(ui, p) {
+                                requested = Some(req);
+                            }
+                        });
+                    }
+                });
+            });
+
+        self.sort_col = next_sort_col;
+        self.sort_desc = next_sort_desc;
+        if let Some(req) = requested {
+            self.request_signal(req);
+        }
+        if clicked.is_some() {
+            self.select(clicked);
+        }
+    }
+
+    fn render_tree(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| self.render_filter_bar(ui));
+        let rows = self.tree_rows();
+        let reset = self.reset_tables.remove("process_tree");
+        let filter = self.active_filter();
+        let text_height = egui::TextStyle::Body.resolve(ui.style()).size + 2.0;
+
+        let sort_col = self.tree_sort;
+        let sort_desc = self.tree_sort_desc;
+        let mut next_sort_col = sort_col;
+        let mut next_sort_desc = sort_desc;
+        let mut header_btn = |ui: &mut egui::Ui, label: &str, col: TreeSort| {
+            let is_sorted = sort_col == col;
+            let text = if is_sorted {
+                format!("{} {}", label, if sort_desc { "⬇" } else { "⬆" })
+            } else {
+                label.to_string()
+            };
+            if ui.selectable_label(is_sorted, text).clicked() {
+                if is_sorted {
+                    next_sort_desc = !next_sort_desc;
+                } else {
+                    next_sort_col = col;
+                    //numele si calea sunt mai utile crescator
+                    next_sort_desc =
+                        !matches!(col, TreeSort::Name | TreeSort::User | TreeSort::Path);
+                }
+            }
+        };
+
+        let mut requested: Option<SignalRequest> = None;
+        let mut clicked: Option<u32> = None;
+        let mut toggled: Option<u32> = None;
+        let live = self.is_local_live();
+
+        table_builder(ui, "process_tree", reset)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(220.0))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(80.0))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(80.0))
+            .column(Column::auto().at_least(50.0))
+            .column(Column::initial(80.0))
+            .column(Column::remainder())
+            .header(20.0, |mut header| {
+                header.col(|ui| header_btn(ui, "Name", TreeSort::Name));
+                header.col(|ui| header_btn(ui, "PID", TreeSort::Pid));
+                header.col(|ui| header_btn(ui, "CPU %", TreeSort::Cpu));
+                header.col(|ui| header_btn(ui, "Mem", TreeSort::Memory));
+                header.col(|ui| header_btn(ui, "Σ CPU %", TreeSort::SubtreeCpu));
+                header.col(|ui| header_btn(ui, "Σ Mem", TreeSort::SubtreeMemory));
+                header.col(|ui| header_btn(ui, "Desc.", TreeSort::Descendants));
+                header.col(|ui| header_btn(ui, "User", TreeSort::User));
+                header.col(|ui| header_btn(ui, "Path", TreeSort::Path));
+            })
+            .body(|body| {
+                body.rows(text_height, rows.len(), |mut row| {
+                    let (pid, depth) = rows[row.index()];
+                    let Some(p) = self.mirror.get(pid) else {
+                        return;
+                    };
+                    let totals = self.subtree.get(&pid).copied().unwrap_or_default();
+                    let has_children = !self.mirror.tree().children(pid).is_empty();
+                    //stramosii care nu se potrivesc apar estompati
+                    let weak = filter.is_some_and(|f| !f.matches(p));
+                    let fresh = self.is_new(pid);
+                    let text = |s: String| {
+                        let t = egui::RichText::new(s);
+                        let t = if fresh {
+                            t.color(NEW_COLOR).strong()
+                        } else {
+                            t
+                        };
+                        if weak { t.weak() } else { t }
+                    };
+                    row.set_selected(self.selected_pid == Some(pid));
+                    row.col(|ui| {
+                        ui.add_space(depth as f32 * 14.0);
+                        if has_children {
+                            let icon = if self.is_expanded(pid) { "⏷" } else { "⏵" };
+                            if ui.add(egui::Button::new(icon).frame(false)).clicked() {
+                                toggled = Some(pid);
+                            }
+                        } else {
+                            ui.add_space(14.0);
+                        }
+                        ui.label(text(p.name.clone()));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(pid.to_string()));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(format!("{:.1}", p.cpu_usage)));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(format!("{:.2} MB", bytes_to_mb(p.memory))));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(format!("{:.1}", totals.cpu)));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(format!("{:.2} MB", bytes_to_mb(totals.memory))));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(totals.descendants.to_string()));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(p.username.clone()));
+                    });
+                    row.col(|ui| {
+                        ui.label(text(p.path.clone()));
+                    });
+                    let response = row.response();
+                    if response.clicked() {
+                        clicked = Some(pid);
+                    }
+                    if response.double_clicked() && has_children {
+                        toggled = Some(pid);
+                    }
+                    if live {
+                        response.context_menu(|ui| {
+                            if let Some(req) = signal_menu(ui, p) {
+                                requested = Some(req);
+                            }
+                        });
+                    }
+                });
+            });
+
+        self.tree_sort = next_sort_col;
+        self.tree_sort_desc = next_sort_desc;
+        if let Some(pid) = toggled {
+            if self.tree_expanded.contains(&pid) {
+                self.tree_expanded.remove(&pid);
+            } else {
+                self.tree_expanded.insert(pid);
+            }
+        }
+        if let Some(req) = requested {
+            self.request_signal(req);
+        }
+        if clicked.is_some() {
+            self.select(clicked);
+        }
+    }
+
+    fn is_new(&self, pid: u32) -> bool {
+        self.mirror.started_within(pid, NEW_HIGHLIGHT)
+    }
+
+    //pornirile si iesirile de procese ale gazdei afisate, cele mai noi primele
+    fn render_events(&mut self, ui: &mut egui::Ui) {
+        let mut clear = false;
+        ui.horizontal(|ui| {
+            let label = LifecycleKind::filter_label;
+            egui::ComboBox::from_id_salt("event_kind")
+                .selected_text(label(self.event_kind))
+                .show_ui(ui, |ui| {
+                    for kind in LifecycleKind::FILTERS {
+                        ui.selectable_value(&mut self.event_kind, kind, label(kind));
+                    }
+                });
+            clear = ui.button("Clear").clicked();
+            ui.separator();
+            self.render_filter_bar(ui);
+        });
+        if clear {
+            self.mirror.clear_events();
+        }
+
+        let filter = self.active_filter();
+        let events: Vec<&ProcessEvent> = self
+            .mirror
+            .events()
+            .iter()
+            .rev()
+            .filter(|e| e.matches(self.event_kind, filter))
+            .collect();
+        if events.is_empty() {
+            ui.label(egui::RichText::new("No process events yet").weak());
+            return;
+        }
+        let text_height = egui::TextStyle::Body.resolve(ui.style()).size + 2.0;
+        let mut clicked = None;
+        table_builder(
+            ui,
+            "process_events",
+            self.reset_tables.remove("process_events"),
+        )
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto().at_least(140.0))
+        .column(Column::auto().at_least(70.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::initial(150.0))
+        .column(Column::initial(150.0))
+        .column(Column::auto().at_least(60.0))
+        .column(Column::auto().at_least(80.0))
+        .column(Column::initial(80.0))
+        .column(Column::remainder())
+        .header(20.0, |mut header| {
+            for title in [
+                "Time", "Event", "PID", "Name", "Parent", "CPU %", "Mem", "User", "Path",
+            ] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(text_height, events.len(), |mut row| {
+                let e = events[row.index()];
+                let p = &e.process;
+                //doar un proces inca viu (acelasi start_time) se poate selecta
+                let alive = self
+                    .mirror
+                    .get(p.pid)
+                    .is_some_and(|cur| cur.start_time == p.start_time);
+                row.set_selected(alive && self.selected_pid == Some(p.pid));
+                row.col(|ui| {
+                    ui.label(format_timestamp(e.timestamp_ms / 1000));
+                });
+                row.col(|ui| {
+                    let (text, color) = match e.kind {
+                        LifecycleKind::Started => ("STARTED", NEW_COLOR),
+                        LifecycleKind::Exited => ("EXITED", Color32::LIGHT_RED),
+                    };
+                    ui.label(egui::RichText::new(text).color(color));
+                });
+                row.col(|ui| {
+                    ui.label(p.pid.to_string());
+                });
+                row.col(|ui| {
+                    ui.label(&p.name);
+                });
+                row.col(|ui| {
+                    let parent = match p.ppid {
+                        Some(ppid) => match self.mirror.get(ppid) {
+                            Some(pp) => format!("{} [{}]", pp.name, ppid),
+                            None => format!("[{}]", ppid),
+                        },
+                        None => "-".to_string(),
+                    };
+                    ui.label(parent);
+                });
+                row.col(|ui| {
+                    ui.label(format!("{:.1}", p.cpu_usage));
+                });
+                row.col(|ui| {
+                    ui.label(format!("{:.2} MB", bytes_to_mb(p.memory)));
+                });
+                row.col(|ui| {
+                    ui.label(&p.username);
+                });
+                row.col(|ui| {
+                    ui.label(&p.path);
+                });
+                if alive && row.response().clicked() {
+                    clicked = Some(p.pid);
+                }
+            });
+        });
+        if let Some(pid) = clicked {
+            self.select(Some(pid));
+        }
+    }
+
+    fn render_groups(&mut self, ui: &mut egui::Ui) {
+        let by = self.group_by;
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("group_by")
+                .selected_text(format!("By {}", self.group_by.label().to_lowercase()))
+                .show_ui(ui, |ui| {
+                    for by in GroupBy::ALL {
+                        ui.selectable_value(&mut self.group_by, by, by.label());
+                    }
+                });
+            ui.separator();
+            self.render_filter_bar(ui);
+        });
+        if self.group_by != by {
+            self.group_expanded.clear();
+        }
+        let Some(data) = self.mirror.data() else {
+            return;
+        };
+        let processes = &data.procese;
+        let reset = self.reset_tables.remove("process_groups");
+        let filter = self.active_filter();
+        let show_kind = self.group_by == GroupBy::Cgroup;
+        let mut groups = groups::group(processes, self.group_by, filter);
+        groups::sort_groups(&mut groups, self.group_sort, self.group_sort_desc);
+        //cu filtru activ grupurile ramase se deschid singure
+        let group_key = |g: &groups::ProcessGroup| format!("{}/{}", g.kind, g.name);
+        let expanded = |key: &str| filter.is_some() || self.group_expanded.contains(key);
+
+        //randuri: (grup, None) pentru antet, (grup, Some(proces)) pentru membri
+        let mut rows: Vec<(usize, Option<usize>)> = Vec::new();
+        for (gi, g) in groups.iter_mut().enumerate() {
+            rows.push((gi, None));
+            if expanded(&group_key(g)) {
+                sort_indices(processes, &mut g.members, SortColumn::Cpu, true);
+                rows.extend(g.members.iter().map(|&i| (gi, Some(i))));
+            }
+        }
+
+        let text_height = egui::TextStyle::Body.resolve(ui.style()).size + 2.0;
+        let sort_col = self.group_sort;
+        let sort_desc = self.group_sort_desc;
+        let mut next_sort_col = sort_col;
+        let mut next_sort_desc = sort_desc;
+        let mut header_btn = |ui: &mut egui::Ui, label: &str, col: GroupSort| {
+            let is_sorted = sort_col == col;
+            let text = if is_sorted {
+                format!("{} {}", label, if sort_desc { "⬇" } else { "⬆" })
+            } else {
+                label.to_string()
+            };
+            if ui.selectable_label(is_sorted, text).clicked() {
+                if is_sorted {
+                    next_sort_desc = !next_sort_desc;
+                } else {
+                    next_sort_col = col;
+                    next_sort_desc = !matches!(col, GroupSort::Name | GroupSort::Kind);
+                }
+            }
+        };
+
+        let mut toggled: Option<String> = None;
+        let mut clicked: Option<u32> = None;
+        let mut requested: Option<SignalRequest> = None;
+        let selected_pid = self.selected_pid;
+        let live = self.is_local_live();
+
+        let mut table = table_builder(ui, "process_groups", reset)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(260.0));
+        //felul grupului are sens doar la cgroup
+        if show_kind {
+            table = table.column(Column::auto().at_least(80.0));
+        }
+        table
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::remainder())
+            .header(20.0, |mut header| {
+                header.col(|ui| header_btn(ui, "Group", GroupSort::Name));
+                if show_kind {
+                    header.col(|ui| header_btn(ui, "Kind", GroupSort::Kind));
+                }
+                header.col(|ui| header_btn(ui, "Procs", GroupSort::Count));
+                header.col(|ui| header_btn(ui, "Threads", GroupSort::Threads));
+                header.col(|ui| header_btn(ui, "CPU %", GroupSort::Cpu));
+                header.col(|ui| header_btn(ui, "Mem", GroupSort::Memory));
+            })
+            .body(|body| {
+                body.rows(text_height, rows.len(), |mut row| {
+                    let (gi, member) = rows[row.index()];
+                    let g = &groups[gi];
+                    match member {
+                        None => {
+                            let key = group_key(g);
+                            row.col(|ui| {
+                                let icon = if expanded(&key) { "⏷" } else { "⏵" };
+                                if ui.add(egui::Button::new(icon).frame(false)).clicked() {
+                                    toggled = Some(key.clone());
+                                }
+                                ui.strong(&g.name);
+                            });
+                            if show_kind {
+                                row.col(|ui| {
+                                    ui.label(&g.kind);
+                                });
+                            }
+                            row.col(|ui| {
+                                ui.label(g.members.len().to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(g.threads.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{:.1}", g.cpu));
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{:.2} MB", bytes_to_mb(g.memory)));
+                            });
+                            if row.response().clicked() {
+                                toggled = Some(key);
+                            }
+                        }
+                        Some(i) => {
+                            let p = &processes[i];
+                            row.set_selected(selected_pid == Some(p.pid));
+                            row.col(|ui| {
+                                ui.add_space(28.0);
+                                ui.label(format!("{} [{}]", p.name, p.pid));
+                            });
+                            if show_kind {
+                                row.col(|_| {});
+                            }
+                            //Procs si Threads raman goale pentru un singur proces
+                            row.col(|_| {});
+                            row.col(|_| {});
+                            row.col(|ui| {
+                                ui.label(format!("{:.1}", p.cpu_usage));
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{:.2} MB", bytes_to_mb(p.memory)));
+                            });
+                            let response = row.response();
+                            if response.clicked() {
+                                clicked = Some(p.pid);
+                            }
+                            if live {
+                                response.context_menu(|ui| {
+                                    if let Some(req) = signal_menu(ui, p) {
+                                        requested = Some(req);
+                                    }
+                                });
+                            }
+                        }
+                    }
+                });
+            });
+
+        self.group_sort = next_sort_col;
+        self.group_sort_desc = next_sort_desc;
+        if let Some(key) = toggled {
+            if self.group_expanded.contains(&key) {
+                self.group_expanded.remove(&key);
+            } else {
+                self.group_expanded.insert(key);
+            }
+        }
+        if let Some(req) = requested {
+            self.request_signal(req);
+        }
+        if clicked.is_some() {
+            self.select(clicked);
+        }
+    }
+
+    //cu filtru activ se vede tot drumul pana la potriviri
+    fn is_expanded(&self, pid: u32) -> bool {
+        self.active_filter().is_some() || self.tree_expanded.contains(&pid)
+    }
+
+    //randurile vizibile (pid, adancime) in ordinea de afisare
+    fn tree_rows(&self) -> Vec<(u32, usize)> {
+        let filter = self.active_filter();
+        let mut rows = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack: Vec<(u32, usize)> = self
+            .sorted_siblings(self.mirror.tree().roots())
+            .into_iter()
+            .rev()
+            .map(|pid| (pid, 0))
+            .collect();
+        while let Some((pid, depth)) = stack.pop() {
+            if !visited.insert(pid) {
+                continue;
+            }
+            if filter.is_some() && !self.filter_visible.contains(&pid) {
+                continue;
+            }
+            rows.push((pid, depth));
+            if self.is_expanded(pid) {
+                let kids = self.mirror.tree().children(pid);
+                for kid in self.sorted_siblings(kids).into_iter().rev() {
+                    stack.push((kid, depth + 1));
+                }
+            }
+        }
+        rows
+    }
+
+    fn sorted_siblings(&self, pids: &[u32]) -> Vec<u32> {
+        let mut sorted = pids.to_vec();
+        let totals = |pid: &u32| self.subtree.get(pid).copied().unwrap_or_default();
+        let proc = |pid: &u32| self.mirror.get(*pid);
+        sorted.sort_by(|a, b| {
+            let cmp = match self.tree_sort {
+                TreeSort::Pid => a.cmp(b),
+                TreeSort::Name => proc(a).map(|p| &p.name).cmp(&proc(b).map(|p| &p.name)),
+                TreeSort::Cpu => proc(a)
+                    .map_or(0.0, |p| p.cpu_usage)
+                    .total_cmp(&proc(b).map_or(0.0, |p| p.cpu_usage)),
+                TreeSort::Memory => proc(a).map(|p| p.memory).cmp(&proc(b).map(|p| p.memory)),
+                TreeSort::User => proc(a)
+                    .map(|p| &p.username)
+                    .cmp(&proc(b).map(|p| &p.username)),
+                TreeSort::Path => proc(a).map(|p| &p.path).cmp(&proc(b).map(|p| &p.path)),
+                TreeSort::SubtreeCpu => totals(a).cpu.total_cmp(&totals(b).cpu),
+                TreeSort::SubtreeMemory => totals(a).memory.cmp(&totals(b).memory),
+                TreeSort::Descendants => totals(a).descendants.cmp(&totals(b).descendants),
+            };
+            if self.tree_sort_desc {
+                cmp.reverse()
+            } else {
+                cmp
+            }
+        });
+        sorted
+    }
+
+    fn render_details(&mut self, ui: &mut egui::Ui) {
+        let Some(pid) = self.selected_pid else {
+            return;
+        };
+        let mut close = false;
+        let mut jump_to: Option<u32> = None;
+
+        ui.horizontal(|ui| {
+            match self.mirror.get(pid) {
+                Some(p) => ui.heading(format!("{} [{}]", p.name, pid)),
+                None => ui.heading(format!("[{}]", pid)),
+            };
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✖").clicked() {
+                    close = true;
+                }
+            });
+        });
+        ui.separator();
+
+        let Some(p) = self.mirror.get(pid) else {
+            ui.label(egui::RichText::new("Process exited").weak());
+            if close {
+                self.select(None);
+            }
+            return;
+        };
+        let details = self
+            .mirror
+            .data()
+            .and_then(|d| d.details.as_ref())
+            .filter(|d| d.pid == pid);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("details_grid")
+                .num_columns(2)
+                .spacing([20.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("User");
+                    ui.label(&p.username);
+                    ui.end_row();
+                    ui.label("CPU");
+                    ui.label(format!("{:.1}%", p.cpu_usage));
+                    ui.end_row();
+                    ui.label("Memory");
+                    ui.label(format!("{:.2} MB", bytes_to_mb(p.memory)));
+                    ui.end_row();
+                    ui.label("Path");
+                    ui.label(&p.path);
+                    ui.end_row();
+                    if let Some(cg) = &p.cgroup {
+                        ui.label("Cgroup");
+                        ui.label(cg);
+                        ui.end_row();
+                    }
+                    if let Some(d) = details {
+                        ui.label("Status");
+                        ui.label(&d.status);
+                        ui.end_row();
+                        ui.label("Started");
+                        ui.label(format_timestamp(d.start_time));
+                        ui.end_row();
+                        ui.label("Run time");
+                        ui.label(format_duration(d.run_time));
+                        ui.end_row();
+                        ui.label("Cwd");
+                        ui.label(d.cwd.as_deref().unwrap_or("Unknown"));
+                        ui.end_row();
+                    }
                 });
-            });
 
-        self.sort_col = next_sort_col;
-        self.sort_desc = next_sort_desc;
-    }
+            let history = self.mirror.data().and_then(|d| d.process_history.get(&pid));
+            if let Some(h) = history {
+                let history_len = self.history_len();
+                let size = egui::vec2(ui.available_width(), 60.0);
+                ui.add_space(10.0);
+                ui.label(egui::RichText::new("CPU history").strong());
+                draw_sparkline(ui, size, &h.cpu, history_len, 1.0, CPU_COLOR);
+                let peak = h.memory.iter().copied().fold(0.0, f32::max);
+                ui.label(egui::RichText::new("Memory history").strong());
+                ui.label(
+                    egui::RichText::new(format!("peak {:.2} MB", bytes_to_mb(peak as u64))).weak(),
+                );
+                draw_sparkline(ui, size, &h.memory, history_len, 0.0, MEM_COLOR);
+            }
 
-    fn render_tree(&self, ui: &mut egui::Ui) {
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            for &pid in &self.radacini {
-                self.render_tree_node(ui, pid);
+            let Some(d) = details else {
+                if self.is_local_live() {
+                    ui.spinner();
+                }
+                return;
+            };
+
+            ui.add_space(10.0);
+            ui.label(egui::RichText::new("Command line").strong());
+            if d.cmd.is_empty() {
+                ui.label(egui::RichText::new("unavailable").weak());
+            } else {
+                ui.label(egui::RichText::new(d.cmd.join(" ")).monospace());
+            }
+
+            ui.add_space(10.0);
+            ui.label(egui::RichText::new("Parent chain").strong());
+            for (depth, (ppid, name)) in self.parent_chain(pid).into_iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add_space(depth as f32 * 10.0);
+                    if ui.link(format!("{} [{}]", name, ppid)).clicked() {
+                        jump_to = Some(ppid);
+                    }
+                });
             }
+
+            ui.add_space(10.0);
+            egui::CollapsingHeader::new(format!("Environment ({})", d.environ.len()))
+                .id_salt("details_env")
+                .show(ui, |ui| {
+                    if d.environ.is_empty() {
+                        ui.label(egui::RichText::new("unavailable").weak());
+                    }
+                    for var in &d.environ {
+                        ui.label(egui::RichText::new(var).monospace().size(11.0));
+                    }
+                });
         });
+
+        if close {
+            self.select(None);
+        } else if jump_to.is_some() {
+            self.select(jump_to);
+        }
     }
 
-    fn render_tree_node(&self, ui: &mut egui::Ui, pid: u32) {
-        if let Some(p) = self.process_map.get(&pid) {
-            let children = self.tree_cache.get(&pid);
-            let has_children;
-            if let Some(v) = children {
-                has_children = !v.is_empty();
-            } else {
-                has_children = false;
+    //de la radacina spre procesul selectat, se opreste la cicluri
+    fn parent_chain(&self, pid: u32) -> Vec<(u32, String)> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut cur = self.mirror.get(pid).and_then(|p| p.ppid);
+        while let Some(ppid) = cur {
+            if !seen.insert(ppid) {
+                break;
+            }
+            let Some(parent) = self.mirror.get(ppid) else {
+                break;
+            };
+            chain.push((ppid, parent.name.clone()));
+            cur = parent.ppid;
+        }
+        chain.reverse();
+        chain
+    }
+
+    //term si kill cer confirmare, stop/cont merg direct
+    fn request_signal(&mut self, req: SignalRequest) {
+        if matches!(req.signal, Signal::Term | Signal::Kill) {
+            self.pending_signal = Some(req);
+        } else {
+            self.apply_signal(req);
+        }
+    }
+
+    fn apply_signal(&mut self, req: SignalRequest) {
+        self.signal_status = Some(
+            send_signal(req.pid, req.signal)
+                .map(|_| {
+                    format!(
+                        "Sent {} to {} [{}]",
+                        signal_label(req.signal),
+                        req.name,
+                        req.pid
+                    )
+                })
+                .map_err(|e| format!("{} [{}]: {}", req.name, req.pid, e)),
+        );
+    }
+
+    fn render_signal_confirm(&mut self, ctx: &egui::Context) {
+        let Some(req) = &self.pending_signal else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("signal_confirm")).show(ctx, |ui| {
+            ui.heading(format!("Send {}?", signal_label(req.signal)));
+            ui.add_space(10.0);
+            ui.label(format!("Process: {} [{}]", req.name, req.pid));
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button("Send").clicked() {
+                    confirmed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+        if confirmed {
+            if let Some(req) = self.pending_signal.take() {
+                self.apply_signal(req);
             }
+        } else if cancelled || modal.should_close() {
+            self.pending_signal = None;
+        }
+    }
 
-            let label = format!("{} [{}] ({:.1}%)", p.name, pid, p.cpu_usage);
+    fn log_alerts(&mut self, events: &[AlertEvent]) {
+        for e in events {
+            self.alert_log.push_back(e.clone());
+        }
+        while self.alert_log.len() > ALERT_LOG_LEN {
+            self.alert_log.pop_front();
+        }
+    }
 
-            if has_children {
-                egui::CollapsingHeader::new(label)
-                    .id_salt(pid)
-                    .show(ui, |ui| {
-                        if let Some(kids) = children {
-                            for &kid_pid in kids {
-                                self.render_tree_node(ui, kid_pid);
-                            }
+    fn render_alert_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(data) = self.mirror.data() else {
+            return;
+        };
+        if data.active_alerts.is_empty() {
+            return;
+        }
+        let mut open_log = false;
+        egui::Frame::new()
+            .fill(Color32::from_rgb(110, 20, 20))
+            .inner_margin(6.0)
+            .corner_radius(4.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        for a in &data.active_alerts {
+                            ui.label(
+                                egui::RichText::new(format!("⚠ {}: {}", a.rule, a.message))
+                                    .color(Color32::WHITE),
+                            );
                         }
                     });
-            } else {
-                ui.label(format!("   {}", label));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                        open_log = ui.button("Alert log").clicked();
+                    });
+                });
+            });
+        if open_log {
+            self.show_alerts = true;
+        }
+    }
+
+    fn render_alerts(&mut self, ctx: &egui::Context) {
+        if !self.show_alerts {
+            return;
+        }
+        let mut open = true;
+        let mut clear = false;
+        let rules = self
+            .backend_config
+            .lock()
+            .map(|c| c.alert_rules.len())
+            .unwrap_or(0);
+        egui::Window::new("Alerts")
+            .open(&mut open)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("{} rules loaded", rules)).weak());
+                    clear = ui.button("Clear").clicked();
+                });
+                ui.separator();
+                if self.alert_log.is_empty() {
+                    ui.label("No alerts yet");
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("alert_log")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            //cele mai noi primele
+                            for e in self.alert_log.iter().rev() {
+                                ui.label(format_timestamp(e.timestamp_ms / 1000));
+                                match e.kind {
+                                    AlertKind::Fired => ui.label(
+                                        egui::RichText::new("FIRED").color(Color32::LIGHT_RED),
+                                    ),
+                                    AlertKind::Resolved => ui.label(
+                                        egui::RichText::new("RESOLVED").color(Color32::LIGHT_GREEN),
+                                    ),
+                                };
+                                ui.label(&e.rule);
+                                ui.label(&e.message);
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        if clear {
+            self.alert_log.clear();
+        }
+        self.show_alerts = open;
+    }
+
+    fn render_signal_status(&mut self, ui: &mut egui::Ui) {
+        let Some(status) = &self.signal_status else {
+            return;
+        };
+        let mut dismiss = false;
+        ui.horizontal(|ui| {
+            match status {
+                Ok(msg) => ui.label(egui::RichText::new(msg).color(Color32::LIGHT_GREEN)),
+                Err(msg) => ui.label(egui::RichText::new(msg).color(Color32::LIGHT_RED)),
+            };
+            if ui.small_button("✖").clicked() {
+                dismiss = true;
             }
+        });
+        if dismiss {
+            self.signal_status = None;
         }
     }
 
@@ -396,6 +1897,14 @@ impl TaskManager {
     }
 
     fn draw_graph(&self, ui: &mut egui::Ui, history: &VecDeque<f32>, color: Color32) {
+        self.draw_graph_lines(ui, &[(history, color)], 100.0);
+    }
+
+    //mai multe serii pe acelasi grafic, scalate la max
+    fn draw_graph_lines(&self, ui: &mut egui::Ui, series: &[(&VecDeque<f32>, Color32)], max: f32) {
+        let Some(&(_, color)) = series.first() else {
+            return;
+        };
         let height = 60.0;
         let width = ui.available_width();
         let (rect, _) = ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::hover());
@@ -410,29 +1919,259 @@ impl TaskManager {
             egui::StrokeKind::Inside,
         );
 
-        if history.len() < 2 {
-            return;
+        for &(history, color) in series {
+            if history.len() < 2 {
+                continue;
+            }
+
+            let points: Vec<Pos2> = history
+                .iter()
+                .enumerate()
+                .map(|(i, &val)| {
+                    let x = rect.min.x + (i as f32 / (history.len() - 1) as f32) * rect.width();
+                    //jos devine sus la grafic
+                    let y = rect.max.y - (val / max).min(1.0) * rect.height();
+                    Pos2::new(x, y)
+                })
+                .collect();
+
+            //linie
+            painter.add(PathShape::line(points, Stroke::new(2.0, color)));
         }
+    }
 
-        let points: Vec<Pos2> = history
+    //load average pe 1/5/15 minute, scara porneste de la numarul de core-uri
+    fn render_load(&self, ui: &mut egui::Ui, global: &Stats) {
+        let colors = [
+            Color32::from_rgb(255, 220, 0),
+            Color32::from_rgb(255, 140, 0),
+            Color32::from_rgb(200, 60, 60),
+        ];
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("LOAD AVERAGE")
+                    .strong()
+                    .color(colors[0]),
+            );
+            for ((label, value), color) in ["1m", "5m", "15m"]
+                .iter()
+                .zip(global.load_average)
+                .zip(colors)
+            {
+                ui.label(
+                    egui::RichText::new(format!("{} {:.2}", label, value))
+                        .monospace()
+                        .color(color),
+                );
+            }
+        });
+        let max = global
+            .load_history
             .iter()
-            .enumerate()
-            .map(|(i, &val)| {
-                let x = rect.min.x + (i as f32 / (history.len() - 1) as f32) * rect.width();
-                //jos devine sus la grafic
-                let y = rect.max.y - (val / 100.0) * rect.height();
-                Pos2::new(x, y)
-            })
-            .collect();
+            .flatten()
+            .fold(global.cores.max(1) as f32, |a, &b| a.max(b));
+        let series: Vec<(&VecDeque<f32>, Color32)> =
+            global.load_history.iter().zip(colors).collect();
+        self.draw_graph_lines(ui, &series, max);
+    }
+
+    fn render_cores(&mut self, ui: &mut egui::Ui, global: &Stats, color: Color32) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("CORES").strong().color(color));
+            ui.checkbox(&mut self.show_core_heatmap, "Heatmap");
+        });
+
+        //numarul de coloane depinde de latime, merge si pt 2 si pt 128 de core-uri
+        let cores = global.core_usage.len();
+        if cores == 0 {
+            return;
+        }
+        let cell_w = 70.0;
+        let cell_h = 16.0;
+        let gap = 4.0;
+        let width = ui.available_width();
+        let cols = (((width + gap) / (cell_w + gap)) as usize).clamp(1, cores);
+        let rows = cores.div_ceil(cols);
+        let (rect, _) = ui.allocate_exact_size(
+            egui::vec2(width, rows as f32 * (cell_h + gap)),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter();
+        for (i, &usage) in global.core_usage.iter().enumerate() {
+            let min = rect.min
+                + egui::vec2((i % cols) as f32, (i / cols) as f32)
+                    * egui::vec2(cell_w + gap, cell_h + gap);
+            let cell = egui::Rect::from_min_size(min, egui::vec2(cell_w, cell_h));
+            painter.rect_filled(cell, 2.0, Color32::from_black_alpha(100));
+            let mut fill = cell;
+            fill.set_width(cell_w * (usage / 100.0).clamp(0.0, 1.0));
+            painter.rect_filled(fill, 2.0, heat_color(usage));
+            painter.text(
+                cell.left_center() + egui::vec2(3.0, 0.0),
+                egui::Align2::LEFT_CENTER,
+                format!("{:>3} {:>3.0}%", i, usage),
+                egui::FontId::monospace(10.0),
+                Color32::WHITE,
+            );
+        }
+
+        if self.show_core_heatmap {
+            ui.add_space(10.0);
+            self.draw_core_heatmap(ui, &global.core_history);
+        }
+    }
+
+    //randuri = core-uri, coloane = timp
+    fn draw_core_heatmap(&self, ui: &mut egui::Ui, history: &[VecDeque<f32>]) {
+        let cores = history.len();
+        let samples = history.iter().map(|h| h.len()).max().unwrap_or(0);
+        if cores == 0 || samples == 0 {
+            return;
+        }
+        let row_h = (240.0 / cores as f32).clamp(2.0, 12.0);
+        let width = ui.available_width();
+        let col_w = width / samples as f32;
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(width, row_h * cores as f32),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter();
+        painter.rect_filled(rect, 0.0, Color32::from_black_alpha(100));
+        for (core, h) in history.iter().enumerate() {
+            for (t, &val) in h.iter().enumerate() {
+                let min = rect.min + egui::vec2(t as f32 * col_w, core as f32 * row_h);
+                painter.rect_filled(
+                    egui::Rect::from_min_size(min, egui::vec2(col_w.ceil(), row_h.ceil())),
+                    0.0,
+                    heat_color(val),
+                );
+            }
+        }
+
+        if let Some(pos) = response.hover_pos() {
+            let core = (((pos.y - rect.min.y) / row_h) as usize).min(cores - 1);
+            let t = (((pos.x - rect.min.x) / col_w) as usize).min(samples - 1);
+            if let Some(val) = history[core].get(t) {
+                let ago = (samples - 1 - t) as f32 * self.sample_interval().as_secs_f32();
+                response.on_hover_text(format!("core {}  -{:.1}s  {:.1}%", core, ago, val));
+            }
+        }
+    }
+
+    fn render_disks(&self, ui: &mut egui::Ui, disks: &[DiskData]) {
+        let color_disk = Color32::from_rgb(255, 215, 0); //galben
+        let color_full = Color32::from_rgb(255, 60, 60);
+
+        ui.label(egui::RichText::new("DISKS").strong().color(color_disk));
+        ui.add_space(5.0);
+        ui.horizontal_wrapped(|ui| {
+            for disk in disks {
+                let used = disk.total_space.saturating_sub(disk.available_space);
+                let used_perc = if disk.total_space > 0 {
+                    (used as f32 / disk.total_space as f32) * 100.0
+                } else {
+                    0.0
+                };
+                //aproape plin -> rosu
+                let color = if used_perc >= 90.0 {
+                    color_full
+                } else {
+                    color_disk
+                };
+                ui.vertical(|ui| {
+                    ui.set_width(140.0);
+                    self.draw_circle_gauge(ui, used_perc, color, &disk.mount_point)
+                        .on_hover_text(&disk.name);
+                    let small = |s: String| egui::RichText::new(s).size(10.0).weak();
+                    ui.label(small(disk.file_system.clone()));
+                    ui.label(small(format!(
+                        "{:.1} GB free of {:.1} GB",
+                        bytes_to_gb(disk.available_space),
+                        bytes_to_gb(disk.total_space)
+                    )));
+                    ui.label(small(format!(
+                        "R {}  W {}",
+                        format_rate(disk.read_rate),
+                        format_rate(disk.write_rate)
+                    )));
+                });
+            }
+        });
+    }
 
-        //linie
-        painter.add(PathShape::line(points, Stroke::new(2.0, color)));
+    fn render_networks(&mut self, ui: &mut egui::Ui, networks: &[InterfaceData]) {
+        let color_rx = Color32::from_rgb(0, 200, 120); //verde
+        let color_tx = Color32::from_rgb(255, 80, 80); //rosu
+
+        ui.label(
+            egui::RichText::new("NETWORK")
+                .strong()
+                .color(Color32::from_rgb(120, 180, 255)),
+        );
+        ui.add_space(5.0);
+        for net in networks {
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.set_width(220.0);
+                    let open = self.show_net_graphs.contains(&net.name);
+                    if ui
+                        .selectable_label(open, egui::RichText::new(&net.name).monospace().strong())
+                        .clicked()
+                    {
+                        if open {
+                            self.show_net_graphs.remove(&net.name);
+                        } else {
+                            self.show_net_graphs.insert(net.name.clone());
+                        }
+                    }
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "↓ {}  ↑ {}",
+                            format_rate(net.rx_rate),
+                            format_rate(net.tx_rate)
+                        ))
+                        .size(12.0),
+                    );
+                    let small = |s: String| egui::RichText::new(s).size(10.0).weak();
+                    ui.label(small(format!(
+                        "pkts {} / {}  err {} / {}",
+                        net.rx_packets, net.tx_packets, net.rx_errors, net.tx_errors
+                    )));
+                    ui.label(small(net.mac.clone()));
+                    for addr in &net.addresses {
+                        ui.label(small(addr.clone()));
+                    }
+                });
+                if self.show_net_graphs.contains(&net.name) {
+                    ui.add_space(20.0);
+                    //scara comuna rx/tx, cel putin 1 KB/s ca sa nu amplificam zgomotul
+                    let max = net
+                        .rx_history
+                        .iter()
+                        .chain(net.tx_history.iter())
+                        .fold(1024.0f32, |a, &b| a.max(b));
+                    ui.vertical(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!("peak {}", format_rate(max as f64)))
+                                .size(10.0)
+                                .weak(),
+                        );
+                        self.draw_graph_lines(
+                            ui,
+                            &[(&net.rx_history, color_rx), (&net.tx_history, color_tx)],
+                            max,
+                        );
+                    });
+                }
+            });
+            ui.add_space(10.0);
+        }
     }
 
     //neofetch
     fn render_overview(&mut self, ui: &mut egui::Ui, global: &Stats) {
-        let color_cpu = Color32::from_rgb(0, 255, 255); //turcoaz
-        let color_mem = Color32::from_rgb(255, 0, 255); //mov
+        let color_cpu = CPU_COLOR;
+        let color_mem = MEM_COLOR;
         let color_swap = Color32::from_rgb(255, 165, 0); //mandariniu
 
         egui::ScrollArea::vertical().show(ui, |ui| {
@@ -457,7 +2196,6 @@ impl TaskManager {
 
             ui.add_space(10.0);
 
-
             egui::Frame::group(ui.style())
                 .fill(Color32::from_black_alpha(50))
                 .stroke(Stroke::new(1.0, Color32::from_white_alpha(30)))
@@ -502,7 +2240,26 @@ impl TaskManager {
                             ui.label(label_fmt("🐧 Kernel"));
                             ui.label(val_fmt(&global.kernel_long_version));
                             ui.label(label_fmt("⌚ Uptime"));
-                            ui.label(val_fmt(global.uptime.to_string().as_str()));
+                            ui.label(val_fmt(&format_duration(global.uptime)));
+                            ui.end_row();
+
+                            ui.label(label_fmt("🚀 Boot"));
+                            ui.label(val_fmt(&format_timestamp(global.boot_time)));
+                            ui.label(label_fmt("⚖ Load"));
+                            let [one, five, fifteen] = global.load_average;
+                            ui.label(val_fmt(&format!("{:.2} {:.2} {:.2}", one, five, fifteen)));
+                            ui.end_row();
+
+                            ui.label(label_fmt("⚙ Processes"));
+                            ui.label(val_fmt(&format!(
+                                "{} ({} threads)",
+                                global.process_count, global.thread_count
+                            )));
+                            ui.label(label_fmt("🔄 State"));
+                            ui.label(val_fmt(&format!(
+                                "{} running, {} sleeping, {} zombie",
+                                global.running, global.sleeping, global.zombies
+                            )));
                             ui.end_row();
                             //ui.label(label_fmt("🌐 ID")); ui.label(val_fmt(&global.distribution_id));
                             //ui.end_row();
@@ -529,6 +2286,10 @@ impl TaskManager {
                     self.draw_graph(ui, &global.cpu_history, color_cpu);
                 }
             });
+            ui.add_space(10.0);
+            self.render_cores(ui, global, color_cpu);
+            ui.add_space(20.0);
+            self.render_load(ui, global);
             ui.add_space(20.0);
 
             //memory
@@ -557,6 +2318,8 @@ impl TaskManager {
                     self.draw_graph(ui, &global.mem_history, color_mem);
                 }
             });
+            ui.add_space(10.0);
+            render_memory_breakdown(ui, global);
             ui.add_space(20.0);
 
             //swap
@@ -594,127 +2357,372 @@ impl TaskManager {
                     self.draw_graph(ui, &global.swap_history, color_swap);
                 }
             });
+            ui.add_space(30.0);
+
+            self.render_disks(ui, &global.disks);
+            ui.add_space(30.0);
+
+            self.render_networks(ui, &global.networks);
         });
     }
 }
 
-fn bytes_to_mb(bytes: u64) -> f64 {
-    bytes as f64 / 1048576.0
+fn sort_indices(processes: &[ProcessData], indices: &mut [usize], col: SortColumn, desc: bool) {
+    match col {
+        SortColumn::Pid => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].pid.cmp(&processes[b].pid);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::Name => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].name.cmp(&processes[b].name);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::Cpu => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].cpu_usage.total_cmp(&processes[b].cpu_usage);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::Memory => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].memory.cmp(&processes[b].memory);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::DiskRead => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].disk_read.cmp(&processes[b].disk_read);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::DiskWrite => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].disk_write.cmp(&processes[b].disk_write);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::VirtualMemory => indices.sort_by(|&a, &b| {
+            let cmp = processes[a]
+                .virtual_memory
+                .cmp(&processes[b].virtual_memory);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::SharedMemory => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].shared_memory.cmp(&processes[b].shared_memory);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+        SortColumn::Swap => indices.sort_by(|&a, &b| {
+            let cmp = processes[a].swap.cmp(&processes[b].swap);
+            if desc { cmp.reverse() } else { cmp }
+        }),
+    }
+}
+
+fn signal_label(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Term => "SIGTERM",
+        Signal::Kill => "SIGKILL",
+        Signal::Stop => "SIGSTOP",
+        Signal::Continue => "SIGCONT",
+        _ => "signal",
+    }
 }
-fn bytes_to_gb(bytes: u64) -> f64 {
-    bytes as f64 / 1073741824.0
+
+fn signal_menu(ui: &mut egui::Ui, p: &ProcessData) -> Option<SignalRequest> {
+    let mut req = None;
+    ui.label(egui::RichText::new(format!("{} [{}]", p.name, p.pid)).strong());
+    ui.separator();
+    for (text, signal) in [
+        ("Terminate", Signal::Term),
+        ("Kill", Signal::Kill),
+        ("Stop", Signal::Stop),
+        ("Continue", Signal::Continue),
+    ] {
+        if ui
+            .button(format!("{} ({})", text, signal_label(signal)))
+            .clicked()
+        {
+            req = Some(SignalRequest {
+                pid: p.pid,
+                name: p.name.clone(),
+                signal,
+            });
+            ui.close();
+        }
+    }
+    req
 }
 
-fn backend(tx: Sender<OverallData>, ctx: egui::Context) {
+//System separat ca sa nu blocam backend-ul, reimprospatam doar pid-ul tinta
+fn send_signal(pid: u32, signal: Signal) -> Result<(), String> {
+    let pid = Pid::from_u32(pid);
     let mut system = System::new();
-    let mut users = Users::new_with_refreshed_list();
-    let mut cpu_history = VecDeque::from(vec![0.0; 60]); // Init with zeros
-    let mut mem_history = VecDeque::from(vec![0.0; 60]);
-    let mut swap_history = VecDeque::from(vec![0.0; 60]);
-    loop {
-        system.refresh_cpu_all();
-        system.refresh_memory();
-        system.refresh_processes_specifics(
-            ProcessesToUpdate::All,
-            true,
-            ProcessRefreshKind::nothing()
-                .with_cpu()
-                .with_exe(sysinfo::UpdateKind::Always)
-                .with_user(sysinfo::UpdateKind::Always)
-                .with_memory(),
-        );
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    let proc = system
+        .process(pid)
+        .ok_or_else(|| "process no longer exists".to_string())?;
+    match proc.kill_with(signal) {
+        Some(true) => Ok(()),
+        Some(false) => {
+            //kill_with intoarce doar bool, errno e inca setat de libc::kill
+            let err = std::io::Error::last_os_error();
+            if err.kind() == std::io::ErrorKind::PermissionDenied {
+                Err("permission denied (process owned by another user?)".to_string())
+            } else {
+                Err(err.to_string())
+            }
+        }
+        None => Err(format!(
+            "{} is not supported on this platform",
+            signal_label(signal)
+        )),
+    }
+}
 
-        users.refresh();
-        let mut processes: Vec<ProcessData> = system
-            .processes()
-            .iter()
-            .map(|(pid, proc)| {
-                let usr = if let Some(uid) = proc.user_id() {
-                    users
-                        .get_user_by_id(uid)
-                        .map(|u| u.name().to_string())
-                        .unwrap_or_else(|| "Unknown".to_string())
-                } else {
-                    "system".to_string()
-                };
-                let path_str: String = if let Some(pth) = proc.exe() {
-                    pth.to_string_lossy().to_string()
-                } else {
-                    "Unknown".to_string()
-                };
-                let proc_name = proc.name().to_string_lossy().to_string();
-                
-                ProcessData {
-                    pid: pid.as_u32(),
-                    name: proc_name,
-                    ppid: proc.parent().map(|p| p.as_u32()),
-                    cpu_usage: proc.cpu_usage() / system.cpus().len() as f32,
-                    memory: proc.memory(),
-                    path: path_str,
-                    username: usr,
-                    is_thread: proc.thread_kind(),
-                }
-            })
-            .collect();
+fn format_duration(secs: u64) -> String {
+    let (d, h, m, s) = (
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+    if d > 0 {
+        format!("{}d {:02}h {:02}m", d, h, m)
+    } else if h > 0 {
+        format!("{}h {:02}m {:02}s", h, m, s)
+    } else {
+        format!("{}m {:02}s", m, s)
+    }
+}
 
-        processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+//fara chrono: conversie zile -> data civila (algoritmul lui Howard Hinnant)
+fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
 
-        cpu_history.push_back(system.global_cpu_usage());
-        if cpu_history.len() > 60 {
-            cpu_history.pop_front();
-        }
+//reset: uita latimile coloanelor salvate de egui pentru tabelul asta
+fn table_builder<'a>(ui: &'a mut egui::Ui, id: &'static str, reset: bool) -> TableBuilder<'a> {
+    let table = TableBuilder::new(ui).id_salt(id);
+    if reset {
+        table.reset();
+    }
+    table
+}
+
+//bara cu memoria folosita de procese, buffers, cache si libera.
+//cache-ul se elibereaza la nevoie, presiunea reala e total - available
+fn render_memory_breakdown(ui: &mut egui::Ui, global: &Stats) {
+    if global.total_memory == 0 {
+        return;
+    }
+    let apps = global
+        .total_memory
+        .saturating_sub(global.free_memory + global.buffers + global.cached);
+    let parts = [
+        ("Apps", apps, MEM_COLOR),
+        ("Buffers", global.buffers, Color32::from_rgb(120, 120, 255)),
+        ("Cached", global.cached, Color32::from_rgb(80, 160, 220)),
+        ("Free", global.free_memory, Color32::from_gray(70)),
+    ];
+    let width = ui.available_width().min(500.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, 14.0), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 3.0, Color32::from_black_alpha(100));
+    let mut x = rect.min.x;
+    for (_, bytes, color) in parts {
+        let w = rect.width() * (bytes as f32 / global.total_memory as f32);
+        let seg = egui::Rect::from_min_max(
+            Pos2::new(x, rect.min.y),
+            Pos2::new((x + w).min(rect.max.x), rect.max.y),
+        );
+        painter.rect_filled(seg, 0.0, color);
+        x += w;
+    }
+    egui::Grid::new("mem_breakdown")
+        .num_columns(5)
+        .spacing([16.0, 2.0])
+        .show(ui, |ui| {
+            for (label, bytes, color) in parts {
+                ui.label(
+                    egui::RichText::new(format!("■ {} {:.2} GB", label, bytes_to_gb(bytes)))
+                        .color(color)
+                        .size(11.0),
+                );
+            }
+            ui.label(
+                egui::RichText::new(format!(
+                    "Available {:.2} GB",
+                    bytes_to_gb(global.available_memory)
+                ))
+                .strong()
+                .size(11.0),
+            );
+        });
+}
+
+//grafic mic aliniat la dreapta, un proces nou ocupa doar capatul din dreapta.
+//scalat la maximul propriu ca sa se vada tendinta si la procesele mici
+fn draw_sparkline(
+    ui: &mut egui::Ui,
+    size: egui::Vec2,
+    history: &VecDeque<f32>,
+    len: usize,
+    min_scale: f32,
+    color: Color32,
+) {
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, Color32::from_black_alpha(100));
+    let len = len.max(history.len());
+    if history.len() < 2 || len < 2 {
+        return;
+    }
+    //min_scale tine procesele aproape inactive ca linie plata, nu zgomot amplificat
+    let max = history
+        .iter()
+        .copied()
+        .fold(min_scale, f32::max)
+        .max(f32::EPSILON);
+    let offset = len - history.len();
+    let points: Vec<Pos2> = history
+        .iter()
+        .enumerate()
+        .map(|(i, &val)| {
+            let x = rect.min.x + ((offset + i) as f32 / (len - 1) as f32) * rect.width();
+            let y = rect.max.y - (val / max).min(1.0) * rect.height();
+            Pos2::new(x, y)
+        })
+        .collect();
+    painter.add(PathShape::line(points, Stroke::new(1.5, color)));
+}
 
-        mem_history.push_back((system.used_memory() as f32 / system.total_memory() as f32) * 100.0);
-        if mem_history.len() > 60 {
-            mem_history.pop_front();
+//albastru inchis -> turcoaz -> galben -> rosu
+fn heat_color(percent: f32) -> Color32 {
+    let stops = [
+        (0.0, [20, 30, 60]),
+        (33.0, [0, 200, 220]),
+        (66.0, [255, 220, 0]),
+        (100.0, [255, 40, 40]),
+    ];
+    let v = percent.clamp(0.0, 100.0);
+    for w in stops.windows(2) {
+        let ((a, ca), (b, cb)) = (w[0], w[1]);
+        if v <= b {
+            let t = (v - a) / (b - a);
+            let mix = |i: usize| (ca[i] as f32 + (cb[i] as f32 - ca[i] as f32) * t) as u8;
+            return Color32::from_rgb(mix(0), mix(1), mix(2));
         }
+    }
+    Color32::from_rgb(255, 40, 40)
+}
 
-        let swap_p = if system.total_swap() > 0 {
-            (system.used_swap() as f32 / system.total_swap() as f32) * 100.0
-        } else {
-            0.0
-        };
-        swap_history.push_back(swap_p);
-        if swap_history.len() > 60 {
-            swap_history.pop_front();
-        }
-
-        let stat = Stats {
-            host_name: System::host_name(),
-            system_name: System::name(),
-            cpu_architecture: System::cpu_arch(),
-            os_version: System::long_os_version(),
-            global_cpu_usage: system.global_cpu_usage(),
-            kernel_long_version: System::kernel_long_version(),
-            cores: system.cpus().len() as u32,
-            distribution_id: System::distribution_id(),
-            used_memory: system.used_memory(),
-            total_memory: system.total_memory(),
-            used_swap: system.used_swap(),
-            total_swap: system.total_swap(),
-            uptime: System::uptime(),
-            cpu_history: cpu_history.clone(),
-            mem_history: mem_history.clone(),
-            swap_history: swap_history.clone(),
-        };
-        ctx.request_repaint();
-        if tx
-            .send(OverallData {
-                procese: processes,
-                stats: stat,
-            })
-            .is_err()
-        {
-            break;
+fn format_rate(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1048576.0 {
+        format!("{:.1} MB/s", bytes_per_sec / 1048576.0)
+    } else if bytes_per_sec >= 1024.0 {
+        format!("{:.1} KB/s", bytes_per_sec / 1024.0)
+    } else {
+        format!("{:.0} B/s", bytes_per_sec)
+    }
+}
+
+//--interval 500ms|2s|1.5, --history N si --alerts <fisier>, valabile pentru toate modurile
+fn extract_backend_flags(args: Vec<String>) -> Result<(BackendConfig, Vec<String>), String> {
+    let mut config = BackendConfig::default();
+    let mut rest = Vec::new();
+    let mut alerts_path = None;
+    let mut it = args.into_iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--interval" => {
+                let v = it.next().ok_or("--interval needs a value")?;
+                config.interval = parse_interval(&v)?;
+            }
+            "--history" => {
+                let v = it.next().ok_or("--history needs a value")?;
+                let len: usize = v
+                    .parse()
+                    .map_err(|_| format!("--history expects a number, got '{}'", v))?;
+                if !(MIN_HISTORY..=MAX_HISTORY).contains(&len) {
+                    return Err(format!(
+                        "--history must be between {} and {} samples",
+                        MIN_HISTORY, MAX_HISTORY
+                    ));
+                }
+                config.history_len = len;
+            }
+            "--alerts" => {
+                alerts_path = Some(it.next().ok_or("--alerts needs a file")?.into());
+            }
+            _ => rest.push(arg),
         }
-        thread::sleep(Duration::from_secs(1));
     }
+    //fisierul implicit e optional, cel dat explicit trebuie sa existe
+    let alerts_path =
+        alerts_path.or_else(|| alerts::default_rules_path().filter(|path| path.exists()));
+    if let Some(path) = alerts_path {
+        config.alert_rules = alerts::load_rules(&path)?;
+    }
+    Ok((config, rest))
+}
+
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let (num, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1.0)
+    } else {
+        (value, 1.0)
+    };
+    let secs = num
+        .parse::<f64>()
+        .map_err(|_| format!("bad interval '{}'", value))?
+        * scale;
+    if !(MIN_INTERVAL.as_secs_f64()..=MAX_INTERVAL.as_secs_f64()).contains(&secs) {
+        return Err("--interval must be between 250ms and 60s".to_string());
+    }
+    Ok(Duration::from_secs_f64(secs))
 }
 
 impl eframe::App for TaskManager {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, SETTINGS_KEY, &self.settings());
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if let Ok(data) = self.rx.try_recv() {
-            self.prepare_data(data);
+        if let Ok(mut cfg) = self.backend_config.lock() {
+            cfg.process_memory = self.mem_columns.shared || self.mem_columns.swap;
+        }
+        self.poll_hosts();
+        self.render_replay_bar(ctx);
+        self.render_settings(ctx);
+        self.render_alerts(ctx);
+
+        if self.selected_pid.is_some()
+            && matches!(
+                self.view,
+                ViewMode::List | ViewMode::Tree | ViewMode::Groups | ViewMode::Events
+            )
+        {
+            egui::SidePanel::right("process_details")
+                .resizable(true)
+                .default_width(280.0)
+                .show(ctx, |ui| self.render_details(ui));
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -726,22 +2734,58 @@ impl eframe::App for TaskManager {
                         ViewMode::List => "List View",
                         ViewMode::Tree => "Tree View",
                         ViewMode::Overview => "Overview",
+                        ViewMode::Groups => "Groups",
+                        ViewMode::Events => "Events",
                     })
                     .show_ui(ui, |ui| {
                         ui.selectable_value(&mut self.view, ViewMode::List, "List View");
                         ui.selectable_value(&mut self.view, ViewMode::Tree, "Tree View");
                         ui.selectable_value(&mut self.view, ViewMode::Overview, "Overview");
+                        ui.selectable_value(&mut self.view, ViewMode::Groups, "Groups");
+                        ui.selectable_value(&mut self.view, ViewMode::Events, "Events");
                     });
+                if self.hosts.len() > 1 {
+                    self.render_host_selector(ui);
+                }
+                if self.replay.is_none() {
+                    ui.separator();
+                    let text = if self.paused {
+                        "▶ Resume"
+                    } else {
+                        "⏸ Pause"
+                    };
+                    if ui.selectable_label(self.paused, text).clicked() {
+                        self.set_paused(!self.paused);
+                    }
+                    if ui
+                        .selectable_label(self.show_settings, "⚙ Settings")
+                        .clicked()
+                    {
+                        self.show_settings = !self.show_settings;
+                    }
+                    if self.paused {
+                        ui.label(egui::RichText::new("PAUSED").color(Color32::YELLOW));
+                    }
+                    self.render_health(ui);
+                }
+                ui.separator();
+                if ui.selectable_label(self.show_alerts, "🔔 Alerts").clicked() {
+                    self.show_alerts = !self.show_alerts;
+                }
             });
             ui.separator();
-            if self.cur_data.is_some() {
+            self.render_alert_banner(ui);
+            self.render_signal_status(ui);
+            if self.mirror.data().is_some() {
                 match self.view {
                     ViewMode::List => {
                         self.render_list(ui);
                     }
                     ViewMode::Tree => self.render_tree(ui),
+                    ViewMode::Groups => self.render_groups(ui),
+                    ViewMode::Events => self.render_events(ui),
                     ViewMode::Overview => {
-                        let stats = self.cur_data.as_ref().map(|d| d.stats.clone());
+                        let stats = self.mirror.data().map(|d| d.stats.clone());
                         if let Some(stats) = stats {
                             self.render_overview(ui, &stats);
                         }
@@ -751,12 +2795,48 @@ impl eframe::App for TaskManager {
                 ui.centered_and_justified(|ui| ui.spinner());
             }
         });
+        self.render_signal_confirm(ctx);
     }
 }
 
-fn main() -> Result<(), eframe::Error> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (config, args) = extract_backend_flags(std::env::args().skip(1).collect())?;
+    if args.first().is_some_and(|a| a == "snapshot") {
+        snapshot::run(&args[1..], config)?;
+        return Ok(());
+    }
+    if args.first().is_some_and(|a| a == "agent") {
+        remote::run_agent(&args[1..], config)?;
+        return Ok(());
+    }
+    if args.first().is_some_and(|a| a == "record") {
+        recording::run(&args[1..], config)?;
+        return Ok(());
+    }
+    if args.iter().any(|a| a == "--export-metrics") {
+        metrics::run(&args, config)?;
+        return Ok(());
+    }
+    if args.iter().any(|a| a == "--tui") {
+        tui::run(config)?;
+        return Ok(());
+    }
+    let remotes: Vec<String> = args
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| *a == "--connect")
+        .map(|(i, _)| args.get(i + 1).cloned().ok_or("--connect needs host:port"))
+        .collect::<Result<_, _>>()?;
+    let replay = match args.iter().position(|a| a == "--replay") {
+        Some(i) => {
+            let path = args.get(i + 1).ok_or("--replay needs a file")?;
+            Some(Replay::open(std::path::Path::new(path)).map_err(|e| format!("{}: {}", path, e))?)
+        }
+        None => None,
+    };
+
     let viewport = egui::ViewportBuilder::default()
-        .with_inner_size([750.0, 750.0])
+        .with_inner_size(DEFAULT_WINDOW_SIZE)
         .with_min_inner_size([750.0, 750.0]);
     let native_options = eframe::NativeOptions {
         viewport,
@@ -765,6 +2845,7 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "Rust Process Monitor",
         native_options,
-        Box::new(|cc| Ok(Box::new(TaskManager::new(cc)))),
-    )
+        Box::new(|cc| Ok(Box::new(TaskManager::new(cc, config, replay, remotes)))),
+    )?;
+    Ok(())
 }
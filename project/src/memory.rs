@@ -0,0 +1,110 @@
+//detalii de memorie citite direct din /proc, sysinfo nu le expune
+//
+//  meminfo:      Buffers, Cached (kB)
+//  <pid>/status: VmSwap, RssFile + RssShmem (kB), suma e coloana SHR din top
+use std::fs;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct MemInfo {
+    pub buffers: u64,
+    pub cached: u64,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ProcessMemory {
+    pub shared: u64,
+    pub swap: u64,
+}
+
+pub fn read_meminfo() -> MemInfo {
+    fs::read_to_string("/proc/meminfo")
+        .map(|s| parse_meminfo(&s))
+        .unwrap_or_default()
+}
+
+pub fn read_process(pid: u32) -> ProcessMemory {
+    fs::read_to_string(format!("/proc/{}/status", pid))
+        .map(|s| parse_status(&s))
+        .unwrap_or_default()
+}
+
+pub fn parse_meminfo(contents: &str) -> MemInfo {
+    let mut info = MemInfo::default();
+    for (key, bytes) in kb_fields(contents) {
+        match key {
+            "Buffers" => info.buffers = bytes,
+            "Cached" => info.cached = bytes,
+            _ => {}
+        }
+    }
+    info
+}
+
+pub fn parse_status(contents: &str) -> ProcessMemory {
+    let mut mem = ProcessMemory::default();
+    for (key, bytes) in kb_fields(contents) {
+        match key {
+            "RssFile" | "RssShmem" => mem.shared += bytes,
+            "VmSwap" => mem.swap = bytes,
+            _ => {}
+        }
+    }
+    mem
+}
+
+//liniile "Cheie:   123 kB", intoarse in bytes
+fn kb_fields(contents: &str) -> impl Iterator<Item = (&str, u64)> {
+    contents.lines().filter_map(|line| {
+        let (key, rest) = line.split_once(':')?;
+        let value = rest.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()?;
+        Some((key, value * 1024))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_meminfo() {
+        let sample = "\
+MemTotal:       16318480 kB
+MemFree:         1021556 kB
+MemAvailable:    9876540 kB
+Buffers:          512000 kB
+Cached:          7340032 kB
+SwapCached:         1024 kB
+HugePages_Total:       0
+";
+        assert_eq!(
+            parse_meminfo(sample),
+            MemInfo {
+                buffers: 512000 * 1024,
+                cached: 7340032 * 1024,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_process_status() {
+        let sample = "\
+Name:\tpostgres
+VmSize:\t  220000 kB
+VmRSS:\t   40000 kB
+RssAnon:\t    8000 kB
+RssFile:\t   12000 kB
+RssShmem:\t   20000 kB
+VmSwap:\t     512 kB
+Threads:\t1
+";
+        assert_eq!(
+            parse_status(sample),
+            ProcessMemory {
+                shared: 32000 * 1024,
+                swap: 512 * 1024,
+            }
+        );
+        //procesele de kernel nu au campurile Vm*
+        assert_eq!(parse_status("Name:\tkthreadd\n"), ProcessMemory::default());
+    }
+}
@@ -0,0 +1,284 @@
+//`project --export-metrics <addr:port> [--metrics-top N]`
+//
+//serveste /metrics in formatul text Prometheus (0.0.4) peste http simplu, fara gui.
+//seriile per proces sunt limitate la primele N dupa cpu plus primele N dupa memorie
+//ca numarul de serii sa ramana marginit; thread-urile nu sunt incluse.
+use std::{
+    fmt::Write as _,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    BackendConfig, OverallData, SortColumn, delta::Mirror, handoff::spawn_backend, sort_indices,
+};
+
+const DEFAULT_TOP: usize = 20;
+
+type Latest = Arc<Mutex<Mirror>>;
+
+pub fn run(args: &[String], config: BackendConfig) -> Result<(), String> {
+    let mut addr = None;
+    let mut top = DEFAULT_TOP;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--export-metrics" => addr = Some(value()?.clone()),
+            "--metrics-top" => {
+                let v = value()?;
+                top = v
+                    .parse()
+                    .map_err(|_| format!("--metrics-top expects a number, got '{}'", v))?;
+            }
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+    let addr = addr.ok_or("usage: project --export-metrics <addr:port> [--metrics-top N]")?;
+    let listener = TcpListener::bind(&addr).map_err(|e| format!("{}: {}", addr, e))?;
+
+    let latest: Latest = Default::default();
+    let rx = spawn_backend(Arc::new(Mutex::new(config)), || {});
+    let shared = latest.clone();
+    thread::spawn(move || {
+        for delta in rx.iter() {
+            if let Ok(mut l) = shared.lock() {
+                l.apply(delta);
+            }
+        }
+        //fara backend ar servi la nesfarsit ultimul esantion
+        eprintln!("{}", rx.health().state);
+        std::process::exit(1);
+    });
+    eprintln!("serving metrics on http://{}/metrics", addr);
+    serve(listener, latest, top);
+    Ok(())
+}
+
+fn serve(listener: TcpListener, latest: Latest, top: usize) {
+    for stream in listener.incoming().flatten() {
+        let latest = latest.clone();
+        thread::spawn(move || {
+            //un client care nu trimite nimic nu tine thread-ul la nesfarsit
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+            let _ = handle(stream, &latest, top);
+        });
+    }
+}
+
+fn handle(mut stream: TcpStream, latest: &Latest, top: usize) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    //restul header-elor nu ne intereseaza, dar trebuie citite
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 && line.trim_end() != "" {
+        line.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (status, body) = if method != "GET" {
+        (
+            "405 Method Not Allowed",
+            "only GET is supported\n".to_string(),
+        )
+    } else if path != "/metrics" {
+        ("404 Not Found", "try /metrics\n".to_string())
+    } else {
+        match latest.lock().ok().as_deref().and_then(Mirror::data) {
+            Some(data) => ("200 OK", render(data, top)),
+            None => (
+                "503 Service Unavailable",
+                "waiting for first sample\n".to_string(),
+            ),
+        }
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn render(data: &OverallData, top: usize) -> String {
+    let s = &data.stats;
+    let mut out = String::new();
+    let mut gauge = |name: &str, help: &str, value: f64| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        let _ = writeln!(out, "{} {}", name, value);
+    };
+    gauge(
+        "process_monitor_cpu_usage_percent",
+        "Global CPU usage.",
+        s.global_cpu_usage as f64,
+    );
+    gauge(
+        "process_monitor_cpu_cores",
+        "Number of logical cores.",
+        s.cores as f64,
+    );
+    gauge(
+        "process_monitor_memory_used_bytes",
+        "Used memory.",
+        s.used_memory as f64,
+    );
+    gauge(
+        "process_monitor_memory_total_bytes",
+        "Total memory.",
+        s.total_memory as f64,
+    );
+    gauge(
+        "process_monitor_swap_used_bytes",
+        "Used swap.",
+        s.used_swap as f64,
+    );
+    gauge(
+        "process_monitor_swap_total_bytes",
+        "Total swap.",
+        s.total_swap as f64,
+    );
+    gauge(
+        "process_monitor_uptime_seconds",
+        "System uptime.",
+        s.uptime as f64,
+    );
+
+    let processes = &data.procese;
+    let mut by_cpu: Vec<usize> = (0..processes.len())
+        .filter(|&i| processes[i].is_thread.is_none())
+        .collect();
+    let mut by_mem = by_cpu.clone();
+    sort_indices(processes, &mut by_cpu, SortColumn::Cpu, true);
+    sort_indices(processes, &mut by_mem, SortColumn::Memory, true);
+    let mut selected: Vec<usize> = by_cpu.into_iter().take(top).collect();
+    for i in by_mem.into_iter().take(top) {
+        if !selected.contains(&i) {
+            selected.push(i);
+        }
+    }
+    selected.sort_by_key(|&i| processes[i].pid);
+
+    let labels = |i: usize| {
+        let p = &processes[i];
+        format!(
+            "pid=\"{}\",name=\"{}\",user=\"{}\"",
+            p.pid,
+            escape_label(&p.name),
+            escape_label(&p.username)
+        )
+    };
+    out.push_str("# HELP process_monitor_process_cpu_usage_percent Per-process CPU usage, normalized to all cores.\n");
+    out.push_str("# TYPE process_monitor_process_cpu_usage_percent gauge\n");
+    for &i in &selected {
+        let _ = writeln!(
+            out,
+            "process_monitor_process_cpu_usage_percent{{{}}} {}",
+            labels(i),
+            processes[i].cpu_usage
+        );
+    }
+    out.push_str("# HELP process_monitor_process_memory_bytes Per-process resident memory.\n");
+    out.push_str("# TYPE process_monitor_process_memory_bytes gauge\n");
+    for &i in &selected {
+        let _ = writeln!(
+            out,
+            "process_monitor_process_memory_bytes{{{}}} {}",
+            labels(i),
+            processes[i].memory
+        );
+    }
+    out
+}
+
+fn escape_label(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::{ProcessData, Stats};
+
+    fn proc(pid: u32, name: &str, cpu: f32, memory: u64) -> ProcessData {
+        ProcessData {
+            pid,
+            name: name.to_string(),
+            ppid: Some(1),
+            cpu_usage: cpu,
+            memory,
+            path: String::new(),
+            username: "root".to_string(),
+            is_thread: None,
+            disk_read: 0,
+            disk_write: 0,
+            cgroup: None,
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            start_time: 0,
+        }
+    }
+
+    fn get(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: test\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let latest: Latest = Default::default();
+        let shared = latest.clone();
+        thread::spawn(move || serve(listener, shared, 1));
+
+        assert!(get(addr, "/metrics").starts_with("HTTP/1.1 503"));
+
+        latest.lock().unwrap().replace(OverallData {
+            procese: vec![
+                proc(10, "busy", 50.0, 1000),
+                proc(11, "big \"db\"", 1.0, 9000),
+                proc(12, "idle", 0.0, 10),
+            ],
+            stats: Stats {
+                global_cpu_usage: 12.5,
+                cores: 4,
+                ..Stats::default()
+            },
+            details: None,
+            alert_events: Vec::new(),
+            active_alerts: Vec::new(),
+            process_events: Vec::new(),
+            process_history: Default::default(),
+        });
+        let response = get(addr, "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("\nprocess_monitor_cpu_usage_percent 12.5\n"));
+        assert!(response.contains("\nprocess_monitor_cpu_cores 4\n"));
+        assert!(response.contains(
+            "process_monitor_process_cpu_usage_percent{pid=\"10\",name=\"busy\",user=\"root\"} 50\n"
+        ));
+        assert!(response.contains(
+            "process_monitor_process_memory_bytes{pid=\"11\",name=\"big \\\"db\\\"\",user=\"root\"} 9000\n"
+        ));
+        //top 1 dupa cpu + top 1 dupa memorie, idle nu apare
+        assert!(!response.contains("name=\"idle\""));
+
+        assert!(get(addr, "/").starts_with("HTTP/1.1 404"));
+    }
+}
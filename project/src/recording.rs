@@ -0,0 +1,495 @@
+//inregistrare si redare de sesiuni
+//
+//format fisier:
+//  header: b"PMREC" + versiune (1 byte) + flags (1 byte, bit 0 = gzip)
+//  cadre:  timestamp ms (u64 LE) + lungime (u32 LE) + OverallData in json (gzip daca e setat)
+//timestamp-ul si lungimea stau in afara payload-ului ca sa putem indexa fisierul
+//fara sa parsam cadrele, redarea citeste doar cadrul cerut.
+//istoricele din Stats nu se scriu (fiecare cadru ar repeta toata fereastra),
+//redarea le reface din valorile cadrelor anterioare.
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::Serialize;
+
+use crate::{
+    BackendConfig, OverallData, ProcessData, ProcessEvent, Stats, alerts::AlertEvent,
+    delta::Mirror, handoff::spawn_backend,
+};
+
+const MAGIC: &[u8; 5] = b"PMREC";
+const VERSION: u8 = 1;
+const FLAG_GZIP: u8 = 1;
+const HEADER_LEN: u64 = 7;
+const FRAME_HEADER_LEN: u64 = 12;
+
+pub struct Recorder {
+    out: BufWriter<File>,
+    gzip: bool,
+}
+
+impl Recorder {
+    //adauga la un fisier existent daca are acelasi tip de compresie. un cadru
+    //trunchiat la final (kill, ctrl-c) e taiat, altfel cadrele noi ar fi citite
+    //ca payload-ul lui
+    pub fn open(path: &Path, gzip: bool) -> io::Result<Recorder> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        if file.metadata()?.len() == 0 {
+            let flags = if gzip { FLAG_GZIP } else { 0 };
+            file.write_all(MAGIC)?;
+            file.write_all(&[VERSION, flags])?;
+        } else {
+            let existing = read_header(&mut file)?;
+            if existing != gzip {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "existing recording uses a different compression setting",
+                ));
+            }
+            let file_len = file.metadata()?.len();
+            let (_, end) = index_frames(&mut BufReader::new(&mut file), file_len)?;
+            if end < file_len {
+                file.set_len(end)?;
+            }
+        }
+        Ok(Recorder {
+            out: BufWriter::new(file),
+            gzip,
+        })
+    }
+
+    pub fn write(&mut self, timestamp_ms: u64, data: &OverallData) -> io::Result<()> {
+        let mut stats = data.stats.clone();
+        clear_histories(&mut stats);
+        let json = serde_json::to_vec(&Frame {
+            procese: &data.procese,
+            stats: &stats,
+            alert_events: &data.alert_events,
+            active_alerts: &data.active_alerts,
+            process_events: &data.process_events,
+        })?;
+        let payload = if self.gzip {
+            let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+            enc.write_all(&json)?;
+            enc.finish()?
+        } else {
+            json
+        };
+        let len = u32::try_from(payload.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "frame too large"))?;
+        self.out.write_all(&timestamp_ms.to_le_bytes())?;
+        self.out.write_all(&len.to_le_bytes())?;
+        self.out.write_all(&payload)?;
+        //flush la fiecare cadru, un kill lasa cel mult ultimul cadru trunchiat
+        self.out.flush()
+    }
+}
+
+//intoarce true daca fisierul e comprimat
+fn read_header(file: &mut File) -> io::Result<bool> {
+    let mut header = [0u8; HEADER_LEN as usize];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut header)?;
+    if &header[..5] != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a process monitor recording",
+        ));
+    }
+    if header[5] != VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported recording version {}", header[5]),
+        ));
+    }
+    Ok(header[6] & FLAG_GZIP != 0)
+}
+
+//un cadru scris, cu aceleasi nume de campuri ca OverallData, fara sa clonam procesele
+#[derive(Serialize)]
+struct Frame<'a> {
+    procese: &'a [ProcessData],
+    stats: &'a Stats,
+    alert_events: &'a [AlertEvent],
+    active_alerts: &'a [AlertEvent],
+    process_events: &'a [ProcessEvent],
+}
+
+fn clear_histories(stats: &mut Stats) {
+    stats.cpu_history.clear();
+    stats.mem_history.clear();
+    stats.swap_history.clear();
+    stats.load_history.iter_mut().for_each(VecDeque::clear);
+    stats.core_history.clear();
+    for net in &mut stats.networks {
+        net.rx_history.clear();
+        net.tx_history.clear();
+    }
+}
+
+//valorile unui cadru care intra in istorice
+#[derive(Clone)]
+struct Point {
+    cpu: f32,
+    mem: f32,
+    swap: f32,
+    load: [f32; 3],
+    cores: Vec<f32>,
+    //nume, rx, tx
+    net: Vec<(String, f32, f32)>,
+}
+
+impl Point {
+    fn new(s: &Stats) -> Self {
+        let percent = |used: u64, total: u64| {
+            if total > 0 {
+                (used as f32 / total as f32) * 100.0
+            } else {
+                0.0
+            }
+        };
+        Point {
+            cpu: s.global_cpu_usage,
+            mem: percent(s.used_memory, s.total_memory),
+            swap: percent(s.used_swap, s.total_swap),
+            load: s.load_average.map(|l| l as f32),
+            cores: s.core_usage.clone(),
+            net: s
+                .networks
+                .iter()
+                .map(|n| (n.name.clone(), n.rx_rate as f32, n.tx_rate as f32))
+                .collect(),
+        }
+    }
+}
+
+struct FrameIndex {
+    timestamp_ms: u64,
+    offset: u64,
+    len: u32,
+}
+
+//cadrele complete si unde se termina ultimul dintre ele
+fn index_frames<R: Read + Seek>(
+    reader: &mut R,
+    file_len: u64,
+) -> io::Result<(Vec<FrameIndex>, u64)> {
+    let mut frames = Vec::new();
+    let mut offset = HEADER_LEN;
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut head = [0u8; FRAME_HEADER_LEN as usize];
+    while offset + FRAME_HEADER_LEN <= file_len {
+        reader.read_exact(&mut head)?;
+        let timestamp_ms = u64::from_le_bytes(head[..8].try_into().unwrap_or_default());
+        let len = u32::from_le_bytes(head[8..].try_into().unwrap_or_default());
+        let payload_at = offset + FRAME_HEADER_LEN;
+        //cadru trunchiat la final, il ignoram
+        if payload_at + len as u64 > file_len {
+            break;
+        }
+        frames.push(FrameIndex {
+            timestamp_ms,
+            offset: payload_at,
+            len,
+        });
+        offset = payload_at + len as u64;
+        reader.seek(SeekFrom::Start(offset))?;
+    }
+    Ok((frames, offset))
+}
+
+pub struct Replay {
+    file: BufReader<File>,
+    gzip: bool,
+    frames: Vec<FrameIndex>,
+    //punctele cadrelor deja citite, ca redarea inainte sa nu reciteasca fereastra
+    points: Vec<Option<Point>>,
+    pub pos: usize,
+    pub playing: bool,
+    pub speed: f32,
+    //timpul virtual al redarii, in ms de la epoch
+    cursor_ms: f64,
+    last_tick: Option<Instant>,
+}
+
+impl Replay {
+    pub fn open(path: &Path) -> io::Result<Replay> {
+        let mut file = File::open(path)?;
+        let gzip = read_header(&mut file)?;
+        let file_len = file.metadata()?.len();
+
+        let mut reader = BufReader::new(file);
+        let (frames, _) = index_frames(&mut reader, file_len)?;
+        if frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "recording has no frames",
+            ));
+        }
+
+        let cursor_ms = frames[0].timestamp_ms as f64;
+        Ok(Replay {
+            file: reader,
+            gzip,
+            points: vec![None; frames.len()],
+            frames,
+            pos: 0,
+            playing: false,
+            speed: 1.0,
+            cursor_ms,
+            last_tick: None,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn timestamp_ms(&self, i: usize) -> u64 {
+        self.frames[i].timestamp_ms
+    }
+
+    //cadrul i cu istoricele refacute din cel mult history_len cadre, el inclusiv
+    pub fn load(&mut self, i: usize, history_len: usize) -> io::Result<OverallData> {
+        let mut data = self.decode(i)?;
+        self.points[i] = Some(Point::new(&data.stats));
+        let start = (i + 1).saturating_sub(history_len);
+        for j in start..i {
+            if self.points[j].is_none() {
+                let old = self.decode(j)?;
+                self.points[j] = Some(Point::new(&old.stats));
+            }
+        }
+        let points: Vec<&Point> = self.points[start..=i].iter().flatten().collect();
+        //ca in Mirror, graficele pornesc pline de zerouri
+        let series = |value: &dyn Fn(&Point) -> f32| -> VecDeque<f32> {
+            let mut h = VecDeque::with_capacity(history_len);
+            h.resize(history_len.saturating_sub(points.len()), 0.0);
+            h.extend(points.iter().map(|p| value(p)));
+            h
+        };
+        let s = &mut data.stats;
+        s.cpu_history = series(&|p| p.cpu);
+        s.mem_history = series(&|p| p.mem);
+        s.swap_history = series(&|p| p.swap);
+        s.load_history = std::array::from_fn(|k| series(&|p| p.load[k]));
+        s.core_history = (0..s.core_usage.len())
+            .map(|c| series(&|p| p.cores.get(c).copied().unwrap_or(0.0)))
+            .collect();
+        for net in &mut s.networks {
+            let rate = |p: &Point, rx: bool| {
+                p.net
+                    .iter()
+                    .find(|n| n.0 == net.name)
+                    .map_or(0.0, |n| if rx { n.1 } else { n.2 })
+            };
+            net.rx_history = series(&|p| rate(p, true));
+            net.tx_history = series(&|p| rate(p, false));
+        }
+        Ok(data)
+    }
+
+    fn decode(&mut self, i: usize) -> io::Result<OverallData> {
+        let frame = &self.frames[i];
+        let mut payload = vec![0u8; frame.len as usize];
+        self.file.seek(SeekFrom::Start(frame.offset))?;
+        self.file.read_exact(&mut payload)?;
+        if self.gzip {
+            let mut json = Vec::new();
+            GzDecoder::new(payload.as_slice()).read_to_end(&mut json)?;
+            payload = json;
+        }
+        Ok(serde_json::from_slice(&payload)?)
+    }
+
+    //dupa mutarea slider-ului
+    pub fn seek(&mut self, i: usize) {
+        self.pos = i.min(self.frames.len() - 1);
+        self.cursor_ms = self.frames[self.pos].timestamp_ms as f64;
+    }
+
+    pub fn set_playing(&mut self, playing: bool) {
+        self.playing = playing;
+        self.last_tick = None;
+        if playing && self.pos + 1 >= self.frames.len() {
+            self.seek(0);
+        }
+    }
+
+    //avanseaza dupa timpul real * viteza, true daca s-a schimbat cadrul
+    pub fn tick(&mut self) -> bool {
+        if !self.playing {
+            return false;
+        }
+        let now = Instant::now();
+        if let Some(last) = self.last_tick {
+            self.cursor_ms += now.duration_since(last).as_secs_f64() * 1000.0 * self.speed as f64;
+        }
+        self.last_tick = Some(now);
+
+        let start = self.pos;
+        while self.pos + 1 < self.frames.len()
+            && self.frames[self.pos + 1].timestamp_ms as f64 <= self.cursor_ms
+        {
+            self.pos += 1;
+        }
+        if self.pos + 1 >= self.frames.len() {
+            self.playing = false;
+        }
+        self.pos != start
+    }
+}
+
+//`project record <fisier> [--gzip]`, ruleaza fara gui pana la ctrl-c
+pub fn run(args: &[String], config: BackendConfig) -> Result<(), String> {
+    let mut path = None;
+    let mut gzip = false;
+    for arg in args {
+        match arg.as_str() {
+            "--gzip" => gzip = true,
+            other if other.starts_with("--") => {
+                return Err(format!("unknown argument '{}'", other));
+            }
+            other => path = Some(other),
+        }
+    }
+    let path = path.ok_or("usage: project record <file> [--gzip]")?;
+    let mut recorder =
+        Recorder::open(Path::new(path), gzip).map_err(|e| format!("{}: {}", path, e))?;
+
+    let rx = spawn_backend(Arc::new(Mutex::new(config)), || {});
+    eprintln!("recording to {} (ctrl-c to stop)", path);
+    //cadrele raman snapshot-uri complete (fara istorice), replay-ul le incarca pe sarite
+    let mut mirror = Mirror::new();
+    for delta in rx.iter() {
+        mirror.apply(delta);
+        let Some(data) = mirror.data() else {
+            continue;
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        recorder
+            .write(now, data)
+            .map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Err(rx.health().state.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProcessData, Stats};
+
+    fn sample(pid: u32) -> OverallData {
+        OverallData {
+            procese: vec![ProcessData {
+                pid,
+                name: "sh".to_string(),
+                ppid: Some(1),
+                cpu_usage: 2.0,
+                memory: 4096,
+                path: "/bin/sh".to_string(),
+                username: "root".to_string(),
+                is_thread: None,
+                disk_read: 0,
+                disk_write: 0,
+                cgroup: None,
+                virtual_memory: 0,
+                shared_memory: 0,
+                swap: 0,
+                start_time: 0,
+            }],
+            stats: Stats::default(),
+            details: None,
+            alert_events: Vec::new(),
+            active_alerts: Vec::new(),
+            process_events: Vec::new(),
+            process_history: Default::default(),
+        }
+    }
+
+    fn roundtrip(gzip: bool) {
+        let path =
+            std::env::temp_dir().join(format!("pmrec-test-{}-{}.rec", std::process::id(), gzip));
+        let _ = std::fs::remove_file(&path);
+        {
+            let mut rec = Recorder::open(&path, gzip).unwrap();
+            rec.write(1000, &sample(10)).unwrap();
+            rec.write(2000, &sample(20)).unwrap();
+        }
+        //append cu alt tip de compresie e refuzat
+        assert!(Recorder::open(&path, !gzip).is_err());
+        Recorder::open(&path, gzip)
+            .unwrap()
+            .write(3000, &sample(30))
+            .unwrap();
+
+        //cadru trunchiat la final
+        let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+        f.write_all(&4000u64.to_le_bytes()).unwrap();
+        f.write_all(&500u32.to_le_bytes()).unwrap();
+        f.write_all(b"{\"proc").unwrap();
+        drop(f);
+
+        let mut replay = Replay::open(&path).unwrap();
+        assert_eq!(replay.len(), 3);
+        assert_eq!(replay.timestamp_ms(2), 3000);
+        assert_eq!(replay.load(1, 60).unwrap().procese[0].pid, 20);
+        assert_eq!(replay.load(0, 60).unwrap().procese[0].pid, 10);
+
+        //reluarea inregistrarii taie cadrul trunchiat
+        Recorder::open(&path, gzip)
+            .unwrap()
+            .write(5000, &sample(50))
+            .unwrap();
+        let mut replay = Replay::open(&path).unwrap();
+        assert_eq!(replay.len(), 4);
+        assert_eq!(replay.timestamp_ms(3), 5000);
+        assert_eq!(replay.load(3, 60).unwrap().procese[0].pid, 50);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn roundtrip_plain() {
+        roundtrip(false);
+    }
+
+    #[test]
+    fn roundtrip_gzip() {
+        roundtrip(true);
+    }
+
+    #[test]
+    fn histories_are_rebuilt_not_stored() {
+        let path = std::env::temp_dir().join(format!("pmrec-hist-{}.rec", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut rec = Recorder::open(&path, false).unwrap();
+        for (i, cpu) in [10.0, 20.0, 30.0].into_iter().enumerate() {
+            let mut data = sample(1);
+            data.stats.global_cpu_usage = cpu;
+            data.stats.cpu_history = vec![cpu; 60].into();
+            rec.write(i as u64 * 1000, &data).unwrap();
+        }
+        drop(rec);
+
+        let mut replay = Replay::open(&path).unwrap();
+        assert!(replay.decode(2).unwrap().stats.cpu_history.is_empty());
+        let cpu = |d: OverallData| -> Vec<f32> { d.stats.cpu_history.into() };
+        assert_eq!(cpu(replay.load(1, 2).unwrap()), [10.0, 20.0]);
+        assert_eq!(cpu(replay.load(2, 4).unwrap()), [0.0, 10.0, 20.0, 30.0]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
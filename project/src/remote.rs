@@ -0,0 +1,356 @@
+//agent la distanta: `project agent <addr:port>` ruleaza backend-ul si trimite
+//esantioanele prin tcp, gui-ul se conecteaza cu `--connect host:port` (se poate repeta)
+//
+//protocol (versiunea 1):
+//  client -> agent: b"PMAG" + versiune (1 byte)
+//  agent -> client: b"PMAG" + versiune (1 byte) + status (0 ok, 1 versiune nesuportata)
+//  apoi agent -> client, la fiecare esantion: lungime (u32 LE) + OverallData in json
+//un client lent primeste doar cel mai nou esantion, nu se aduna coada in agent.
+//details si istoricul per proces nu se trimit (sunt serde(skip)).
+use std::{
+    io::{self, BufReader, BufWriter, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    BackendConfig, OverallData,
+    delta::{Differ, Mirror},
+    handoff::{SampleSender, spawn_backend},
+};
+
+const MAGIC: &[u8; 4] = b"PMAG";
+const VERSION: u8 = 1;
+const STATUS_OK: u8 = 0;
+const STATUS_BAD_VERSION: u8 = 1;
+//un esantion cu mii de procese are cativa MB, orice peste asta e corupt
+const MAX_FRAME: u32 = 64 * 1024 * 1024;
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+//un agent mort fara sa inchida conexiunea (curent, nat) altfel ne-ar bloca la
+//nesfarsit: pana vedem ritmul lui asteptam READ_TIMEOUT, apoi cateva intervale
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+const MISSED_FRAMES: u32 = 5;
+const MIN_READ_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HostState {
+    Connecting,
+    Connected,
+    Failed(String),
+}
+
+//ultimul cadru serializat, cu un numar de secventa ca fiecare client sa stie ce a trimis
+#[derive(Default)]
+struct Broadcast {
+    frame: Mutex<(u64, Arc<Vec<u8>>)>,
+    ready: Condvar,
+}
+
+impl Broadcast {
+    fn publish(&self, data: &OverallData) -> io::Result<()> {
+        let json = serde_json::to_vec(data)?;
+        if let Ok(mut frame) = self.frame.lock() {
+            *frame = (frame.0 + 1, Arc::new(json));
+        }
+        self.ready.notify_all();
+        Ok(())
+    }
+
+    fn wait_newer(&self, seen: u64) -> Option<(u64, Arc<Vec<u8>>)> {
+        let guard = self.frame.lock().ok()?;
+        let guard = self.ready.wait_while(guard, |f| f.0 <= seen).ok()?;
+        Some(guard.clone())
+    }
+}
+
+pub fn run_agent(args: &[String], config: BackendConfig) -> Result<(), String> {
+    let addr = match args {
+        [addr] => addr,
+        _ => return Err("usage: project agent <addr:port>".to_string()),
+    };
+    let listener = TcpListener::bind(addr).map_err(|e| format!("{}: {}", addr, e))?;
+    let broadcast = Arc::new(Broadcast::default());
+
+    let rx = spawn_backend(Arc::new(Mutex::new(config)), || {});
+    let shared = broadcast.clone();
+    thread::spawn(move || {
+        //pe fir pleaca tot snapshot-uri complete, protocolul ramane v1
+        let mut mirror = Mirror::new();
+        for delta in rx.iter() {
+            mirror.apply(delta);
+            let Some(data) = mirror.data() else {
+                continue;
+            };
+            if let Err(e) = shared.publish(data) {
+                eprintln!("cannot encode sample: {}", e);
+            }
+        }
+        //clientii ar primi la nesfarsit acelasi esantion
+        eprintln!("{}", rx.health().state);
+        std::process::exit(1);
+    });
+    eprintln!("agent listening on {} (protocol v{})", addr, VERSION);
+    serve(listener, broadcast);
+    Ok(())
+}
+
+fn serve(listener: TcpListener, broadcast: Arc<Broadcast>) {
+    for stream in listener.incoming().flatten() {
+        let broadcast = broadcast.clone();
+        thread::spawn(move || {
+            let peer = stream
+                .peer_addr()
+                .map(|a| a.to_string())
+                .unwrap_or_default();
+            if let Err(e) = serve_client(stream, &broadcast) {
+                eprintln!("{}: {}", peer, e);
+            }
+        });
+    }
+}
+
+fn serve_client(mut stream: TcpStream, broadcast: &Broadcast) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut hello = [0u8; 5];
+    stream.read_exact(&mut hello)?;
+    if &hello[..4] != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a monitor client",
+        ));
+    }
+    let status = if hello[4] == VERSION {
+        STATUS_OK
+    } else {
+        STATUS_BAD_VERSION
+    };
+    stream.write_all(MAGIC)?;
+    stream.write_all(&[VERSION, status])?;
+    if status != STATUS_OK {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("client speaks protocol v{}", hello[4]),
+        ));
+    }
+
+    let mut out = BufWriter::new(stream);
+    let mut seen = 0;
+    while let Some((seq, frame)) = broadcast.wait_newer(seen) {
+        seen = seq;
+        out.write_all(&(frame.len() as u32).to_le_bytes())?;
+        out.write_all(&frame)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn handshake(stream: &mut TcpStream, version: u8) -> io::Result<()> {
+    stream.write_all(MAGIC)?;
+    stream.write_all(&[version])?;
+    let mut reply = [0u8; 6];
+    stream.read_exact(&mut reply)?;
+    if &reply[..4] != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a monitor agent",
+        ));
+    }
+    if reply[5] != STATUS_OK {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("agent speaks protocol v{}, we speak v{}", reply[4], version),
+        ));
+    }
+    Ok(())
+}
+
+fn read_frame(reader: &mut impl Read) -> io::Result<OverallData> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len);
+    if len > MAX_FRAME {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes is too large", len),
+        ));
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload)?;
+    Ok(serde_json::from_slice(&payload)?)
+}
+
+//ruleaza pe un thread al gui-ului, se reconecteaza singur pana se inchide receptorul
+pub fn connect(addr: String, tx: SampleSender, notify: impl Fn(), state: Arc<Mutex<HostState>>) {
+    let set_state = |s: HostState| {
+        if let Ok(mut cur) = state.lock() {
+            *cur = s;
+        }
+        notify();
+    };
+    loop {
+        set_state(HostState::Connecting);
+        let result = (|| -> io::Result<()> {
+            let mut stream = connect_timeout(&addr)?;
+            stream.set_read_timeout(Some(READ_TIMEOUT))?;
+            handshake(&mut stream, VERSION)?;
+            set_state(HostState::Connected);
+            let mut reader = BufReader::new(stream);
+            //la fiecare conexiune gui-ul o ia de la zero (delta cu reset)
+            let mut differ = Differ::new();
+            //primul cadru e cel deja publicat de agent, vine imediat
+            let mut last_frame: Option<Instant> = None;
+            loop {
+                let data = read_frame(&mut reader)?;
+                if let Some(t) = last_frame {
+                    let timeout = (t.elapsed() * MISSED_FRAMES).max(MIN_READ_TIMEOUT);
+                    reader.get_ref().set_read_timeout(Some(timeout))?;
+                }
+                last_frame = Some(Instant::now());
+                //fereastra agentului; istoricele lui vin cu primul cadru, apoi se
+                //continua aici din punctele noi
+                let history_len = data.stats.cpu_history.len().max(1);
+                let now_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                if !tx.send(differ.diff(data, history_len, now_ms)) {
+                    return Ok(());
+                }
+                notify();
+            }
+        })();
+        match result {
+            //gui-ul s-a inchis
+            Ok(()) => return,
+            Err(e) => set_state(HostState::Failed(e.to_string())),
+        }
+        thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+//TcpStream::connect nu are timeout; incercam pe rand adresele rezolvate
+fn connect_timeout(addr: &str) -> io::Result<TcpStream> {
+    let mut last = io::Error::new(io::ErrorKind::InvalidInput, "address resolves to nothing");
+    for a in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&a, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last = e,
+        }
+    }
+    Err(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProcessData, Stats, handoff::handoff};
+
+    fn sample(pid: u32) -> OverallData {
+        OverallData {
+            procese: vec![ProcessData {
+                pid,
+                name: "sshd".to_string(),
+                ppid: Some(1),
+                cpu_usage: 0.5,
+                memory: 8192,
+                path: "/usr/sbin/sshd".to_string(),
+                username: "root".to_string(),
+                is_thread: None,
+                disk_read: 0,
+                disk_write: 0,
+                cgroup: Some("/system.slice/ssh.service".to_string()),
+                virtual_memory: 0,
+                shared_memory: 0,
+                swap: 0,
+                start_time: 0,
+            }],
+            stats: Stats {
+                host_name: Some("lab1".to_string()),
+                ..Stats::default()
+            },
+            details: None,
+            alert_events: Vec::new(),
+            active_alerts: Vec::new(),
+            process_events: Vec::new(),
+            process_history: Default::default(),
+        }
+    }
+
+    fn agent() -> (std::net::SocketAddr, Arc<Broadcast>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let broadcast = Arc::new(Broadcast::default());
+        let shared = broadcast.clone();
+        thread::spawn(move || serve(listener, shared));
+        (addr, broadcast)
+    }
+
+    #[test]
+    fn streams_samples_over_loopback() {
+        let (addr, broadcast) = agent();
+        broadcast.publish(&sample(10)).unwrap();
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        handshake(&mut stream, VERSION).unwrap();
+        let mut reader = BufReader::new(stream);
+        let first = read_frame(&mut reader).unwrap();
+        assert_eq!(first.procese[0].pid, 10);
+        assert_eq!(first.stats.host_name.as_deref(), Some("lab1"));
+
+        broadcast.publish(&sample(20)).unwrap();
+        let second = read_frame(&mut reader).unwrap();
+        assert_eq!(second.procese[0].pid, 20);
+        assert_eq!(
+            second.procese[0].cgroup.as_deref(),
+            Some("/system.slice/ssh.service")
+        );
+    }
+
+    #[test]
+    fn rejects_other_protocol_versions() {
+        let (addr, _broadcast) = agent();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let err = handshake(&mut stream, VERSION + 1).unwrap_err();
+        assert!(err.to_string().contains("protocol v1"));
+    }
+
+    #[test]
+    fn client_thread_forwards_frames() {
+        let (addr, broadcast) = agent();
+        broadcast.publish(&sample(30)).unwrap();
+        let (tx, rx) = handoff();
+        let state = Arc::new(Mutex::new(HostState::Connecting));
+        let shared = state.clone();
+        thread::spawn(move || connect(addr.to_string(), tx, || {}, shared));
+        let delta = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(delta.reset);
+        assert_eq!(delta.added[0].pid, 30);
+        assert_eq!(*state.lock().unwrap(), HostState::Connected);
+    }
+
+    //agentul tace fara sa inchida conexiunea: clientul renunta dupa cateva intervale
+    #[test]
+    fn silent_agent_times_out() {
+        let (addr, broadcast) = agent();
+        broadcast.publish(&sample(40)).unwrap();
+        let (tx, rx) = handoff();
+        let state = Arc::new(Mutex::new(HostState::Connecting));
+        let shared = state.clone();
+        thread::spawn(move || connect(addr.to_string(), tx, || {}, shared));
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        broadcast.publish(&sample(41)).unwrap();
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let start = Instant::now();
+        while !matches!(*state.lock().unwrap(), HostState::Failed(_)) {
+            assert!(
+                start.elapsed() < MIN_READ_TIMEOUT * 3,
+                "client never gave up"
+            );
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
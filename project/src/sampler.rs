@@ -0,0 +1,334 @@
+//transforma esantioanele brute dintr-un MetricsSource in OverallData:
+//rate din contoare, cpu per proces normalizat la numarul de core-uri
+//si totalurile de procese. istoricele nu se mai tin aici, le reface
+//consumatorul din delta-uri (delta.rs)
+use std::collections::VecDeque;
+
+use sysinfo::{ProcessStatus, ThreadKind};
+
+use crate::{DiskData, InterfaceData, OverallData, ProcessData, Stats, source::Sample};
+
+//elapsed: secunde de la esantionul anterior, None la primul (contoarele sunt totaluri)
+pub fn assemble(sample: Sample, elapsed: Option<f64>) -> OverallData {
+    let rate = |bytes: u64| elapsed.map_or(0.0, |e| bytes as f64 / e.max(0.001));
+    let sys = sample.system;
+    let cores = sys.core_usage.len().max(1) as f32;
+
+    let (mut process_count, mut thread_count, mut running, mut sleeping, mut zombies) =
+        (0, 0, 0, 0, 0);
+    let mut processes: Vec<ProcessData> = sample
+        .processes
+        .into_iter()
+        .map(|p| {
+            if p.is_thread == Some(ThreadKind::Userland) {
+                thread_count += 1;
+            } else {
+                process_count += 1;
+                match p.status {
+                    ProcessStatus::Run => running += 1,
+                    ProcessStatus::Sleep | ProcessStatus::Idle => sleeping += 1,
+                    ProcessStatus::Zombie => zombies += 1,
+                    _ => {}
+                }
+            }
+            ProcessData {
+                pid: p.pid,
+                name: p.name,
+                ppid: p.ppid,
+                cpu_usage: p.cpu_usage / cores,
+                memory: p.memory,
+                path: p.path,
+                username: p.username,
+                is_thread: p.is_thread,
+                disk_read: rate(p.read_bytes) as u64,
+                disk_write: rate(p.written_bytes) as u64,
+                cgroup: p.cgroup,
+                virtual_memory: p.virtual_memory,
+                shared_memory: p.shared_memory,
+                swap: p.swap,
+                start_time: p.start_time,
+            }
+        })
+        .collect();
+    processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+
+    let mut networks: Vec<InterfaceData> = sys
+        .networks
+        .into_iter()
+        .map(|net| InterfaceData {
+            name: net.name,
+            mac: net.mac,
+            addresses: net.addresses,
+            rx_rate: rate(net.received),
+            tx_rate: rate(net.transmitted),
+            rx_packets: net.rx_packets,
+            tx_packets: net.tx_packets,
+            rx_errors: net.rx_errors,
+            tx_errors: net.tx_errors,
+            rx_history: VecDeque::new(),
+            tx_history: VecDeque::new(),
+        })
+        .collect();
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let disks = sys
+        .disks
+        .into_iter()
+        .map(|d| DiskData {
+            name: d.name,
+            mount_point: d.mount_point,
+            file_system: d.file_system,
+            total_space: d.total_space,
+            available_space: d.available_space,
+            read_rate: rate(d.read_bytes),
+            write_rate: rate(d.written_bytes),
+        })
+        .collect();
+
+    let stats = Stats {
+        host_name: sys.host_name,
+        system_name: sys.system_name,
+        cpu_architecture: sys.cpu_architecture,
+        os_version: sys.os_version,
+        global_cpu_usage: sys.global_cpu_usage,
+        kernel_long_version: sys.kernel_long_version,
+        cores: sys.core_usage.len() as u32,
+        distribution_id: sys.distribution_id,
+        used_memory: sys.used_memory,
+        total_memory: sys.total_memory,
+        used_swap: sys.used_swap,
+        total_swap: sys.total_swap,
+        available_memory: sys.available_memory,
+        free_memory: sys.free_memory,
+        buffers: sys.buffers,
+        cached: sys.cached,
+        uptime: sys.uptime,
+        boot_time: sys.boot_time,
+        load_average: sys.load_average,
+        process_count,
+        thread_count,
+        running,
+        sleeping,
+        zombies,
+        core_usage: sys.core_usage,
+        networks,
+        disks,
+        ..Default::default()
+    };
+    OverallData {
+        procese: processes,
+        stats,
+        details: None,
+        alert_events: Vec::new(),
+        active_alerts: Vec::new(),
+        process_events: Vec::new(),
+        process_history: Default::default(),
+    }
+}
+
+pub fn push_history(history: &mut VecDeque<f32>, value: f32, len: usize) {
+    history.push_back(value);
+    while history.len() > len {
+        history.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{
+        BackendConfig,
+        delta::{Differ, Mirror},
+        handoff::spawn_source,
+        source::{DiskSample, NetworkSample, ProcessSample, ScriptedSource, SystemSample},
+    };
+
+    fn proc(pid: u32, cpu: f32, start_time: u64) -> ProcessSample {
+        ProcessSample {
+            pid,
+            ppid: Some(1),
+            name: format!("p{}", pid),
+            path: format!("/bin/p{}", pid),
+            username: "root".to_string(),
+            is_thread: None,
+            status: ProcessStatus::Sleep,
+            start_time,
+            cpu_usage: cpu,
+            memory: 1024 * pid as u64,
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            read_bytes: 4000,
+            written_bytes: 0,
+            cgroup: None,
+        }
+    }
+
+    fn sample(processes: Vec<ProcessSample>) -> Sample {
+        Sample {
+            system: SystemSample {
+                host_name: Some("ci".to_string()),
+                global_cpu_usage: 50.0,
+                core_usage: vec![50.0; 4],
+                used_memory: 1,
+                total_memory: 4,
+                load_average: [1.0, 0.5, 0.25],
+                networks: vec![NetworkSample {
+                    name: "eth0".to_string(),
+                    received: 2000,
+                    ..Default::default()
+                }],
+                disks: vec![DiskSample {
+                    name: "sda".to_string(),
+                    read_bytes: 6000,
+                    written_bytes: 8000,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            processes,
+        }
+    }
+
+    //backend -> delta -> consumator, ca in run_backend
+    fn mirror(samples: Vec<Sample>, history_len: usize) -> Mirror {
+        let (mut differ, mut mirror) = (Differ::new(), Mirror::new());
+        for s in samples {
+            mirror.apply(differ.diff(assemble(s, Some(1.0)), history_len, 0));
+        }
+        mirror
+    }
+
+    #[test]
+    fn history_is_windowed() {
+        let samples: Vec<Sample> = [10.0, 20.0, 30.0, 40.0, 50.0]
+            .into_iter()
+            .map(|cpu| {
+                let mut s = sample(vec![proc(7, cpu, 100)]);
+                s.system.global_cpu_usage = cpu;
+                s
+            })
+            .collect();
+        let mut m = mirror(samples, 3);
+        let data = m.data().unwrap();
+        assert_eq!(data.stats.cpu_history, [30.0, 40.0, 50.0]);
+        assert_eq!(data.stats.mem_history, [25.0, 25.0, 25.0]);
+        assert_eq!(data.stats.load_history[0], [1.0, 1.0, 1.0]);
+        assert_eq!(data.stats.networks[0].rx_history.len(), 3);
+        let h = &data.process_history[&7];
+        assert_eq!(h.cpu, [7.5, 10.0, 12.5]);
+
+        //fereastra se poate micsora din setari
+        let mut differ = Differ::new();
+        differ.diff(assemble(sample(vec![proc(7, 50.0, 100)]), None), 3, 0);
+        let delta = differ.diff(assemble(sample(vec![proc(7, 0.0, 100)]), None), 2, 0);
+        assert!(!delta.reset);
+        m.apply(delta);
+        let data = m.data().unwrap();
+        assert_eq!(data.stats.cpu_history, [50.0, 50.0]);
+        assert_eq!(data.process_history[&7].cpu, [12.5, 0.0]);
+    }
+
+    #[test]
+    fn process_history_resets_on_pid_reuse() {
+        let m = mirror(
+            vec![
+                sample(vec![proc(7, 40.0, 100), proc(8, 0.0, 5)]),
+                sample(vec![proc(7, 80.0, 200)]),
+            ],
+            5,
+        );
+        let data = m.data().unwrap();
+        assert_eq!(data.process_history[&7].start_time, 200);
+        assert_eq!(data.process_history[&7].cpu, [20.0]);
+        assert!(!data.process_history.contains_key(&8));
+    }
+
+    #[test]
+    fn cpu_is_normalized_to_cores() {
+        let data = assemble(
+            sample(vec![proc(1, 400.0, 0), proc(2, 100.0, 0), proc(3, 0.0, 0)]),
+            None,
+        );
+        assert_eq!(data.stats.cores, 4);
+        let cpu: Vec<(u32, f32)> = data.procese.iter().map(|p| (p.pid, p.cpu_usage)).collect();
+        assert_eq!(cpu, [(1, 100.0), (2, 25.0), (3, 0.0)]);
+
+        //fara core-uri raportate nu impartim la zero
+        let mut s = sample(vec![proc(1, 30.0, 0)]);
+        s.system.core_usage.clear();
+        assert_eq!(assemble(s, None).procese[0].cpu_usage, 30.0);
+    }
+
+    #[test]
+    fn assembles_snapshot() {
+        let mut thread = proc(11, 0.0, 0);
+        thread.is_thread = Some(ThreadKind::Userland);
+        thread.ppid = Some(10);
+        let mut zombie = proc(12, 0.0, 0);
+        zombie.status = ProcessStatus::Zombie;
+        let mut busy = proc(13, 0.0, 0);
+        busy.status = ProcessStatus::Run;
+        let s = sample(vec![proc(10, 0.0, 0), thread, zombie, busy]);
+
+        //primul esantion are contoare de la pornire, nu rate: toate ratele sunt 0
+        let first = assemble(s.clone(), None);
+        assert!(
+            first
+                .procese
+                .iter()
+                .all(|p| p.disk_read == 0 && p.disk_write == 0)
+        );
+        assert_eq!(first.stats.networks[0].rx_rate, 0.0);
+        let disk = &first.stats.disks[0];
+        assert_eq!((disk.read_rate, disk.write_rate), (0.0, 0.0));
+
+        let data = assemble(s, Some(2.0));
+        let stats = &data.stats;
+        assert_eq!(stats.host_name.as_deref(), Some("ci"));
+        assert_eq!(
+            (stats.process_count, stats.thread_count),
+            (3, 1),
+            "threads are counted apart"
+        );
+        assert_eq!((stats.running, stats.sleeping, stats.zombies), (1, 1, 1));
+        assert_eq!(stats.networks[0].rx_rate, 1000.0);
+        assert!(data.procese.iter().all(|p| p.disk_read == 2000));
+        assert_eq!(stats.disks[0].write_rate, 4000.0);
+    }
+
+    #[test]
+    fn backend_runs_on_a_scripted_source() {
+        let source = ScriptedSource::new(vec![
+            sample(vec![proc(10, 40.0, 0)]),
+            sample(vec![proc(10, 80.0, 0), proc(20, 4.0, 0)]),
+        ]);
+        //intervalul lung tine primul esantion pe loc pana il citim
+        let config = Arc::new(Mutex::new(BackendConfig {
+            interval: Duration::from_secs(3600),
+            selected_pid: Some(20),
+            ..Default::default()
+        }));
+        let rx = spawn_source(move || source, config.clone(), || {});
+        let mut mirror = Mirror::new();
+        mirror.apply(rx.recv_timeout(Duration::from_secs(5)).unwrap());
+        let first = mirror.data().unwrap();
+        assert_eq!(first.procese.len(), 1);
+        assert!(first.details.is_none());
+        config.lock().unwrap().interval = Duration::from_millis(1);
+        let delta = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(delta.added.iter().any(|p| p.pid == 20));
+        mirror.apply(delta);
+        let second = mirror.data().unwrap();
+        assert_eq!(mirror.get(10).unwrap().cpu_usage, 20.0);
+        assert_eq!(second.details.as_ref().map(|d| d.pid), Some(20));
+        //ultimul esantion se repeta, istoricul poate avea deja mai multe puncte
+        let cpu = &second.process_history[&10].cpu;
+        assert_eq!((cpu[0], cpu[cpu.len() - 1]), (10.0, 20.0));
+    }
+}
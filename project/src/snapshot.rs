@@ -0,0 +1,317 @@
+//`project snapshot --format json|csv|table [--top N] [--sort cpu|mem] [--threads]`
+//
+//ia un singur esantion din backend (al doilea, primul nu are procentele de cpu valide)
+//si il afiseaza fara gui. Schema e stabila, campurile noi se adauga doar la final.
+//
+//json (schema_version 1):
+//  {
+//    "schema_version": 1,
+//    "timestamp": <secunde unix>,
+//    "stats": {
+//      "host_name", "system_name", "os_version": string|null,
+//      "cpu_architecture", "kernel_long_version", "distribution_id": string,
+//      "global_cpu_usage": procent 0-100, "cores": int,
+//      "used_memory", "total_memory", "used_swap", "total_swap": bytes,
+//      "uptime": secunde,
+//      "cpu_history", "mem_history", "swap_history": procente, cel mai vechi primul,
+//      "core_usage": procent per core, "core_history": [[procente]] per core,
+//      "networks": [{"name", "mac", "addresses", "rx_rate", "tx_rate" (bytes/s),
+//                    "rx_packets", "tx_packets", "rx_errors", "tx_errors",
+//                    "rx_history", "tx_history" (bytes/s)}],
+//      "disks": [{"name", "mount_point", "file_system", "total_space",
+//                 "available_space" (bytes), "read_rate", "write_rate" (bytes/s)}],
+//      "available_memory", "free_memory", "buffers", "cached": bytes (din /proc/meminfo),
+//      "boot_time": secunde unix,
+//      "load_average": [1m, 5m, 15m], "load_history": [[valori]] pe aceleasi trei ferestre,
+//      "process_count", "thread_count", "running", "sleeping", "zombies": int
+//    },
+//    "processes": [{
+//      "pid": int, "ppid": int|null, "name", "path", "username": string,
+//      "cpu_usage": procent 0-100 normalizat la numarul de core-uri,
+//      "memory": bytes rss, "is_thread": null|"Kernel"|"Userland",
+//      "disk_read", "disk_write": bytes/s,
+//      "cgroup": calea din /proc/<pid>/cgroup|null,
+//      "virtual_memory", "shared_memory", "swap": bytes,
+//      "start_time": secunde unix (0 daca nu se stie)
+//    }]
+//  }
+//
+//csv: header + un rand pe proces, coloanele din CSV_HEADER
+//table: text pentru oameni, fara garantii de format
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use crate::{
+    BackendConfig, OverallData, ProcessData, SortColumn, Stats, bytes_to_gb, bytes_to_mb,
+    delta::Mirror, format_duration, handoff::spawn_backend, sort_indices,
+};
+
+pub const SCHEMA_VERSION: u32 = 1;
+const CSV_HEADER: &str =
+    "pid,ppid,name,user,cpu_percent,memory_bytes,disk_read_bps,disk_write_bps,thread,path";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Format {
+    Json,
+    Csv,
+    Table,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    format: Format,
+    top: Option<usize>,
+    sort: SortColumn,
+    threads: bool,
+}
+
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    schema_version: u32,
+    timestamp: u64,
+    stats: &'a Stats,
+    processes: Vec<&'a ProcessData>,
+}
+
+pub fn run(args: &[String], config: BackendConfig) -> Result<(), String> {
+    let opts = parse_args(args)?;
+
+    let rx = spawn_backend(Arc::new(Mutex::new(config)), || {});
+    //primul esantion e doar incalzire pentru cpu
+    let mut mirror = Mirror::new();
+    for _ in 0..2 {
+        mirror.apply(rx.recv().ok_or_else(|| rx.health().state.to_string())?);
+    }
+    let data = mirror.data().ok_or("no sample")?;
+
+    print!("{}", render(data, &opts)?);
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut opts = Options {
+        format: Format::Table,
+        top: None,
+        sort: SortColumn::Cpu,
+        threads: false,
+    };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--format" => {
+                opts.format = match value()?.as_str() {
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    "table" => Format::Table,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "--top" => {
+                let v = value()?;
+                opts.top = Some(
+                    v.parse()
+                        .map_err(|_| format!("--top expects a number, got '{}'", v))?,
+                );
+            }
+            "--sort" => {
+                opts.sort = match value()?.as_str() {
+                    "cpu" => SortColumn::Cpu,
+                    "mem" => SortColumn::Memory,
+                    other => return Err(format!("unknown sort '{}' (cpu or mem)", other)),
+                }
+            }
+            "--threads" => opts.threads = true,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+    Ok(opts)
+}
+
+fn select_processes<'a>(data: &'a OverallData, opts: &Options) -> Vec<&'a ProcessData> {
+    let processes = &data.procese;
+    let mut indices: Vec<usize> = processes
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| opts.threads || p.is_thread.is_none())
+        .map(|(i, _)| i)
+        .collect();
+    sort_indices(processes, &mut indices, opts.sort, true);
+    if let Some(top) = opts.top {
+        indices.truncate(top);
+    }
+    indices.into_iter().map(|i| &processes[i]).collect()
+}
+
+fn render(data: &OverallData, opts: &Options) -> Result<String, String> {
+    let processes = select_processes(data, opts);
+    match opts.format {
+        Format::Json => {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let snapshot = Snapshot {
+                schema_version: SCHEMA_VERSION,
+                timestamp,
+                stats: &data.stats,
+                processes,
+            };
+            serde_json::to_string_pretty(&snapshot)
+                .map(|s| s + "\n")
+                .map_err(|e| e.to_string())
+        }
+        Format::Csv => Ok(render_csv(&processes)),
+        Format::Table => Ok(render_table(&data.stats, &processes)),
+    }
+}
+
+fn render_csv(processes: &[&ProcessData]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for p in processes {
+        let thread = match p.is_thread {
+            Some(sysinfo::ThreadKind::Kernel) => "kernel",
+            Some(sysinfo::ThreadKind::Userland) => "userland",
+            None => "",
+        };
+        let row = [
+            p.pid.to_string(),
+            p.ppid.map(|v| v.to_string()).unwrap_or_default(),
+            csv_field(&p.name),
+            csv_field(&p.username),
+            format!("{:.2}", p.cpu_usage),
+            p.memory.to_string(),
+            p.disk_read.to_string(),
+            p.disk_write.to_string(),
+            thread.to_string(),
+            csv_field(&p.path),
+        ];
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn render_table(stats: &Stats, processes: &[&ProcessData]) -> String {
+    let mut out = format!(
+        "{}  cpu {:.1}%  mem {:.1}/{:.1} GB  swap {:.1}/{:.1} GB  uptime {}  load {:.2} {:.2} {:.2}\n\n",
+        stats.host_name.as_deref().unwrap_or(""),
+        stats.global_cpu_usage,
+        bytes_to_gb(stats.used_memory),
+        bytes_to_gb(stats.total_memory),
+        bytes_to_gb(stats.used_swap),
+        bytes_to_gb(stats.total_swap),
+        format_duration(stats.uptime),
+        stats.load_average[0],
+        stats.load_average[1],
+        stats.load_average[2]
+    );
+    out.push_str(&format!(
+        "{:>8} {:<24} {:>6} {:>12} {:<12} {}\n",
+        "PID", "NAME", "CPU %", "MEM", "USER", "PATH"
+    ));
+    for p in processes {
+        out.push_str(&format!(
+            "{:>8} {:<24} {:>6.1} {:>9.2} MB {:<12} {}\n",
+            p.pid,
+            truncate(&p.name, 24),
+            p.cpu_usage,
+            bytes_to_mb(p.memory),
+            truncate(&p.username, 12),
+            p.path
+        ));
+    }
+    out
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        s.chars().take(max - 1).chain(['…']).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_flags() {
+        let opts = parse_args(&args("--format csv --top 5 --sort mem")).unwrap();
+        assert_eq!(
+            opts,
+            Options {
+                format: Format::Csv,
+                top: Some(5),
+                sort: SortColumn::Memory,
+                threads: false,
+            }
+        );
+        assert!(parse_args(&args("--format xml")).is_err());
+        assert!(parse_args(&args("--top")).is_err());
+        assert!(parse_args(&args("--sort pid")).is_err());
+    }
+
+    //campurile adaugate dupa schema_version 1 stau dupa cele vechi
+    #[test]
+    fn json_keeps_the_original_stats_order() {
+        let json = serde_json::to_string(&Stats::default()).unwrap();
+        let at = |key: &str| json.find(&format!("\"{}\":", key)).unwrap();
+        let original = [
+            "total_swap",
+            "uptime",
+            "cpu_history",
+            "swap_history",
+            "disks",
+        ];
+        assert!(original.windows(2).all(|w| at(w[0]) < at(w[1])));
+        assert!(at("disks") < at("available_memory"));
+        assert!(at("disks") < at("boot_time"));
+    }
+
+    #[test]
+    fn csv_is_stable_and_escaped() {
+        let p = ProcessData {
+            pid: 7,
+            name: "a,\"b\"".to_string(),
+            ppid: None,
+            cpu_usage: 1.5,
+            memory: 1024,
+            path: "/bin/x".to_string(),
+            username: "root".to_string(),
+            is_thread: None,
+            disk_read: 3,
+            disk_write: 4,
+            cgroup: None,
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            start_time: 0,
+        };
+        assert_eq!(
+            render_csv(&[&p]),
+            format!(
+                "{}\n7,,\"a,\"\"b\"\"\",root,1.50,1024,3,4,,/bin/x\n",
+                CSV_HEADER
+            )
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//de unde vin esantioanele: MetricsSource da valorile brute (contoare, cpu nenormalizat),
+//sampler.rs le transforma in OverallData. SysinfoSource citeste sistemul,
+//ScriptedSource (doar in teste) reda esantioane scrise de mana.
+use std::collections::HashMap;
+
+use sysinfo::{
+    Disks, Networks, Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, ThreadKind,
+    UpdateKind, Users,
+};
+
+use crate::{ProcessDetails, cgroup, memory};
+
+pub trait MetricsSource {
+    fn sample(&mut self) -> Sample;
+    //cmd, cwd si environ sunt scumpe, se cer doar pentru procesul selectat
+    fn details(&mut self, pid: u32) -> Option<ProcessDetails>;
+    //shared si swap per proces (o citire de /proc/<pid>/status fiecare), oprite
+    //cand niciun consumator nu le arata
+    fn set_process_memory(&mut self, _enabled: bool) {}
+}
+
+#[derive(Clone, Debug)]
+pub struct Sample {
+    pub system: SystemSample,
+    pub processes: Vec<ProcessSample>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SystemSample {
+    pub host_name: Option<String>,
+    pub system_name: Option<String>,
+    pub cpu_architecture: String,
+    pub os_version: Option<String>,
+    pub kernel_long_version: String,
+    pub distribution_id: String,
+    pub global_cpu_usage: f32,
+    pub core_usage: Vec<f32>,
+    pub used_memory: u64,
+    pub total_memory: u64,
+    pub used_swap: u64,
+    pub total_swap: u64,
+    pub available_memory: u64,
+    pub free_memory: u64,
+    pub buffers: u64,
+    pub cached: u64,
+    pub uptime: u64,
+    pub boot_time: u64,
+    pub load_average: [f64; 3],
+    pub networks: Vec<NetworkSample>,
+    pub disks: Vec<DiskSample>,
+}
+
+//received/transmitted/read/written sunt bytes de la esantionul anterior
+#[derive(Clone, Debug, Default)]
+pub struct NetworkSample {
+    pub name: String,
+    pub mac: String,
+    pub addresses: Vec<String>,
+    pub received: u64,
+    pub transmitted: u64,
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DiskSample {
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total_space: u64,
+    pub available_space: u64,
+    pub read_bytes: u64,
+    pub written_bytes: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProcessSample {
+    pub pid: u32,
+    pub ppid: Option<u32>,
+    pub name: String,
+    pub path: String,
+    pub username: String,
+    pub is_thread: Option<ThreadKind>,
+    pub status: ProcessStatus,
+    pub start_time: u64,
+    //suma pe core-uri, ca in sysinfo: 4 core-uri ocupate = 400
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub virtual_memory: u64,
+    pub shared_memory: u64,
+    pub swap: u64,
+    pub read_bytes: u64,
+    pub written_bytes: u64,
+    pub cgroup: Option<String>,
+}
+
+pub struct SysinfoSource {
+    system: System,
+    users: Users,
+    networks: Networks,
+    disks: Disks,
+    //cgroup-ul unui proces aproape nu se schimba: pid -> (start_time, cale)
+    cgroups: HashMap<u32, (u64, Option<String>)>,
+    process_memory: bool,
+}
+
+impl SysinfoSource {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            users: Users::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            cgroups: HashMap::new(),
+            process_memory: true,
+        }
+    }
+}
+
+impl Default for SysinfoSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsSource for SysinfoSource {
+    fn sample(&mut self) -> Sample {
+        let system = &mut self.system;
+        system.refresh_cpu_all();
+        system.refresh_memory();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cpu()
+                .with_exe(UpdateKind::Always)
+                .with_user(UpdateKind::Always)
+                .with_memory()
+                .with_disk_usage(),
+        );
+        self.users.refresh();
+
+        let processes = system
+            .processes()
+            .iter()
+            .map(|(pid, proc)| {
+                let usr = if let Some(uid) = proc.user_id() {
+                    self.users
+                        .get_user_by_id(uid)
+                        .map(|u| u.name().to_string())
+                        .unwrap_or_else(|| "Unknown".to_string())
+                } else {
+                    "system".to_string()
+                };
+                let path_str: String = if let Some(pth) = proc.exe() {
+                    pth.to_string_lossy().to_string()
+                } else {
+                    "Unknown".to_string()
+                };
+                let disk = proc.disk_usage();
+                let is_thread = proc.thread_kind();
+                //thread-urile impart memoria procesului, nu mai citim /proc pentru ele
+                let (mem, cgroup) = if is_thread.is_none() {
+                    let mem = if self.process_memory {
+                        memory::read_process(pid.as_u32())
+                    } else {
+                        Default::default()
+                    };
+                    let start = proc.start_time();
+                    let cached = self
+                        .cgroups
+                        .entry(pid.as_u32())
+                        .and_modify(|c| {
+                            if c.0 != start {
+                                *c = (start, cgroup::read(pid.as_u32()));
+                            }
+                        })
+                        .or_insert_with(|| (start, cgroup::read(pid.as_u32())));
+                    (mem, cached.1.clone())
+                } else {
+                    Default::default()
+                };
+                ProcessSample {
+                    pid: pid.as_u32(),
+                    ppid: proc.parent().map(|p| p.as_u32()),
+                    name: proc.name().to_string_lossy().to_string(),
+                    path: path_str,
+                    username: usr,
+                    is_thread,
+                    status: proc.status(),
+                    start_time: proc.start_time(),
+                    cpu_usage: proc.cpu_usage(),
+                    memory: proc.memory(),
+                    virtual_memory: proc.virtual_memory(),
+                    shared_memory: mem.shared,
+                    swap: mem.swap,
+                    read_bytes: disk.read_bytes,
+                    written_bytes: disk.written_bytes,
+                    cgroup,
+                }
+            })
+            .collect();
+        let live = system.processes();
+        self.cgroups
+            .retain(|pid, _| live.contains_key(&Pid::from_u32(*pid)));
+
+        self.networks.refresh(true);
+        let networks = self
+            .networks
+            .list()
+            .iter()
+            .map(|(name, net)| NetworkSample {
+                name: name.clone(),
+                mac: net.mac_address().to_string(),
+                addresses: net
+                    .ip_networks()
+                    .iter()
+                    .map(|ip| format!("{}/{}", ip.addr, ip.prefix))
+                    .collect(),
+                received: net.received(),
+                transmitted: net.transmitted(),
+                rx_packets: net.total_packets_received(),
+                tx_packets: net.total_packets_transmitted(),
+                rx_errors: net.total_errors_on_received(),
+                tx_errors: net.total_errors_on_transmitted(),
+            })
+            .collect();
+
+        self.disks.refresh(true);
+        let disks = self
+            .disks
+            .list()
+            .iter()
+            .map(|d| {
+                let usage = d.usage();
+                DiskSample {
+                    name: d.name().to_string_lossy().to_string(),
+                    mount_point: d.mount_point().to_string_lossy().to_string(),
+                    file_system: d.file_system().to_string_lossy().to_string(),
+                    total_space: d.total_space(),
+                    available_space: d.available_space(),
+                    read_bytes: usage.read_bytes,
+                    written_bytes: usage.written_bytes,
+                }
+            })
+            .collect();
+
+        let meminfo = memory::read_meminfo();
+        let load = System::load_average();
+        let system = &self.system;
+        Sample {
+            system: SystemSample {
+                host_name: System::host_name(),
+                system_name: System::name(),
+                cpu_architecture: System::cpu_arch(),
+                os_version: System::long_os_version(),
+                kernel_long_version: System::kernel_long_version(),
+                distribution_id: System::distribution_id(),
+                global_cpu_usage: system.global_cpu_usage(),
+                core_usage: system.cpus().iter().map(|c| c.cpu_usage()).collect(),
+                used_memory: system.used_memory(),
+                total_memory: system.total_memory(),
+                used_swap: system.used_swap(),
+                total_swap: system.total_swap(),
+                available_memory: system.available_memory(),
+                free_memory: system.free_memory(),
+                buffers: meminfo.buffers,
+                cached: meminfo.cached,
+                uptime: System::uptime(),
+                boot_time: System::boot_time(),
+                load_average: [load.one, load.five, load.fifteen],
+                networks,
+                disks,
+            },
+            processes,
+        }
+    }
+
+    fn set_process_memory(&mut self, enabled: bool) {
+        self.process_memory = enabled;
+    }
+
+    fn details(&mut self, pid: u32) -> Option<ProcessDetails> {
+        let sys_pid = Pid::from_u32(pid);
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[sys_pid]),
+            false,
+            ProcessRefreshKind::nothing()
+                .with_cmd(UpdateKind::Always)
+                .with_cwd(UpdateKind::Always)
+                .with_environ(UpdateKind::Always),
+        );
+        let proc = self.system.process(sys_pid)?;
+        let to_strings = |v: &[std::ffi::OsString]| {
+            v.iter()
+                .map(|s| s.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+        Some(ProcessDetails {
+            pid,
+            cmd: to_strings(proc.cmd()),
+            cwd: proc.cwd().map(|p| p.to_string_lossy().to_string()),
+            environ: to_strings(proc.environ()),
+            status: proc.status().to_string(),
+            start_time: proc.start_time(),
+            run_time: proc.run_time(),
+        })
+    }
+}
+
+//reda esantioanele in ordine, apoi il repeta pe ultimul
+#[cfg(test)]
+pub struct ScriptedSource {
+    samples: Vec<Sample>,
+    pos: usize,
+}
+
+#[cfg(test)]
+impl ScriptedSource {
+    pub fn new(samples: Vec<Sample>) -> Self {
+        assert!(!samples.is_empty(), "a script needs at least one sample");
+        Self { samples, pos: 0 }
+    }
+}
+
+#[cfg(test)]
+impl MetricsSource for ScriptedSource {
+    fn sample(&mut self) -> Sample {
+        let sample = self.samples[self.pos.min(self.samples.len() - 1)].clone();
+        self.pos += 1;
+        sample
+    }
+
+    fn details(&mut self, pid: u32) -> Option<ProcessDetails> {
+        let current = &self.samples[self.pos.saturating_sub(1).min(self.samples.len() - 1)];
+        let p = current.processes.iter().find(|p| p.pid == pid)?;
+        Some(ProcessDetails {
+            pid,
+            cmd: vec![p.path.clone()],
+            cwd: None,
+            environ: Vec::new(),
+            status: p.status.to_string(),
+            start_time: p.start_time,
+            run_time: 0,
+        })
+    }
+}
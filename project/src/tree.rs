@@ -0,0 +1,376 @@
+//arborele de procese: radacini + copii pe fiecare pid, folosit de gui si de tui.
+//build_tree il face de la zero, ProcessTree il tine la zi din delta-uri
+use std::collections::{HashMap, HashSet};
+
+use crate::ProcessData;
+
+//totalurile unui nod cu tot cu descendenti, fara thread-uri (ar dubla memoria)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SubtreeTotals {
+    pub cpu: f32,
+    pub memory: u64,
+    pub descendants: usize,
+}
+//un proces al carui parinte nu mai exista (parinte fantoma), e propriul parinte
+//sau are un parinte pornit dupa el (pid refolosit) devine radacina.
+//ciclurile de parinti (esantion prins la mijlocul unei reparentari) se rup
+//facand radacina din primul pid al ciclului intalnit in lista
+pub fn build_tree(processes: &[ProcessData]) -> (Vec<u32>, HashMap<u32, Vec<u32>>) {
+    let by_pid: HashMap<u32, &ProcessData> = processes.iter().map(|p| (p.pid, p)).collect();
+    let mut radacini = Vec::new();
+    let mut tree: HashMap<u32, Vec<u32>> = HashMap::new();
+    let mut parent: HashMap<u32, u32> = HashMap::new();
+    for p in processes {
+        match p.ppid.and_then(|ppid| by_pid.get(&ppid)) {
+            Some(&pp) if pp.pid != p.pid && !started_after(pp, p) => {
+                tree.entry(pp.pid).or_default().push(p.pid);
+                parent.insert(p.pid, pp.pid);
+            }
+            _ => radacini.push(p.pid),
+        }
+    }
+
+    //ce nu se vede din radacini sta intr-un ciclu sau sub unul
+    let mut reached = HashSet::new();
+    let mark = |from: u32, reached: &mut HashSet<u32>, tree: &HashMap<u32, Vec<u32>>| {
+        let mut stack = vec![from];
+        while let Some(pid) = stack.pop() {
+            if reached.insert(pid) {
+                stack.extend(tree.get(&pid).into_iter().flatten());
+            }
+        }
+    };
+    for &r in &radacini {
+        mark(r, &mut reached, &tree);
+    }
+    for p in processes {
+        if reached.contains(&p.pid) {
+            continue;
+        }
+        //urcam pana revenim pe un pid deja vazut, acela e pe ciclu
+        let mut seen = HashSet::new();
+        let mut cur = p.pid;
+        while seen.insert(cur) {
+            cur = parent[&cur];
+        }
+        let pp = parent.remove(&cur).unwrap_or(cur);
+        if let Some(kids) = tree.get_mut(&pp) {
+            kids.retain(|&k| k != cur);
+        }
+        radacini.push(cur);
+        mark(cur, &mut reached, &tree);
+    }
+    (radacini, tree)
+}
+
+//arborele tinut incremental: un proces adaugat/sters/reparentat muta doar muchiile lui.
+//aceleasi reguli ca build_tree; un ciclu se rupe la procesul care l-ar inchide
+#[derive(Clone, Debug, Default)]
+pub struct ProcessTree {
+    roots: Vec<u32>,
+    children: HashMap<u32, Vec<u32>>,
+    parent: HashMap<u32, u32>,
+}
+
+impl ProcessTree {
+    pub fn build(processes: &[ProcessData]) -> Self {
+        let (roots, children) = build_tree(processes);
+        let parent = children
+            .iter()
+            .flat_map(|(&pp, kids)| kids.iter().map(move |&k| (k, pp)))
+            .collect();
+        Self {
+            roots,
+            children,
+            parent,
+        }
+    }
+
+    pub fn roots(&self) -> &[u32] {
+        &self.roots
+    }
+
+    pub fn children(&self, pid: u32) -> &[u32] {
+        self.children
+            .get(&pid)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn parent(&self, pid: u32) -> Option<u32> {
+        self.parent.get(&pid).copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.roots.clear();
+        self.children.clear();
+        self.parent.clear();
+    }
+
+    //get gaseste procesele deja la zi, inclusiv pe cel nou
+    pub fn insert<'a>(&mut self, pid: u32, get: impl Fn(u32) -> Option<&'a ProcessData>) {
+        self.link(pid, &get);
+        //orfanii care il asteptau ca parinte (copilul a venit inaintea lui)
+        let Some(p) = get(pid) else {
+            return;
+        };
+        let waiting: Vec<u32> = self
+            .roots
+            .iter()
+            .copied()
+            .filter(|&r| r != pid && get(r).is_some_and(|c| c.ppid == Some(pid)))
+            .collect();
+        for r in waiting {
+            if get(r).is_some_and(|c| !started_after(p, c)) && !self.is_ancestor(r, pid) {
+                self.roots.retain(|&x| x != r);
+                self.children.entry(pid).or_default().push(r);
+                self.parent.insert(r, pid);
+            }
+        }
+    }
+
+    //copiii lui raman fara parinte si devin radacini
+    pub fn remove(&mut self, pid: u32) {
+        self.detach(pid);
+        for kid in self.children.remove(&pid).unwrap_or_default() {
+            self.parent.remove(&kid);
+            self.roots.push(kid);
+        }
+    }
+
+    //ppid schimbat (reparentare la init sau la un subreaper)
+    pub fn relink<'a>(&mut self, pid: u32, get: impl Fn(u32) -> Option<&'a ProcessData>) {
+        self.detach(pid);
+        self.link(pid, &get);
+    }
+
+    fn detach(&mut self, pid: u32) {
+        match self.parent.remove(&pid) {
+            Some(pp) => {
+                if let Some(kids) = self.children.get_mut(&pp) {
+                    kids.retain(|&k| k != pid);
+                }
+            }
+            None => self.roots.retain(|&r| r != pid),
+        }
+    }
+
+    fn link<'a>(&mut self, pid: u32, get: &impl Fn(u32) -> Option<&'a ProcessData>) {
+        let Some(p) = get(pid) else {
+            return;
+        };
+        match p.ppid.and_then(get) {
+            Some(pp)
+                if pp.pid != pid && !started_after(pp, p) && !self.is_ancestor(pid, pp.pid) =>
+            {
+                self.children.entry(pp.pid).or_default().push(pid);
+                self.parent.insert(pid, pp.pid);
+            }
+            _ => self.roots.push(pid),
+        }
+    }
+
+    //arborele nu are cicluri, urcarea se termina mereu
+    fn is_ancestor(&self, ancestor: u32, mut pid: u32) -> bool {
+        loop {
+            if pid == ancestor {
+                return true;
+            }
+            match self.parent.get(&pid) {
+                Some(&pp) => pid = pp,
+                None => return false,
+            }
+        }
+    }
+}
+
+//start_time 0 inseamna necunoscut (esantioane vechi), atunci avem incredere in ppid
+fn started_after(parent: &ProcessData, child: &ProcessData) -> bool {
+    parent.start_time != 0 && child.start_time != 0 && parent.start_time > child.start_time
+}
+
+//post-order iterativ, un pid vizitat de doua ori (ciclu) e ignorat
+pub fn subtree_totals<'a>(
+    tree: &ProcessTree,
+    get: impl Fn(u32) -> Option<&'a ProcessData>,
+) -> HashMap<u32, SubtreeTotals> {
+    let mut totals: HashMap<u32, SubtreeTotals> = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut stack: Vec<(u32, bool)> = tree.roots().iter().map(|&pid| (pid, false)).collect();
+    while let Some((pid, children_done)) = stack.pop() {
+        let kids = tree.children(pid);
+        if !children_done {
+            if visited.insert(pid) {
+                stack.push((pid, true));
+                stack.extend(kids.iter().map(|&kid| (kid, false)));
+            }
+            continue;
+        }
+        let mut t = match get(pid) {
+            Some(p) if p.is_thread.is_none() => SubtreeTotals {
+                cpu: p.cpu_usage,
+                memory: p.memory,
+                descendants: 0,
+            },
+            _ => SubtreeTotals::default(),
+        };
+        for kid in kids {
+            let is_process = get(*kid).is_some_and(|p| p.is_thread.is_none());
+            if let Some(k) = totals.get(kid) {
+                t.cpu += k.cpu;
+                t.memory += k.memory;
+                t.descendants += k.descendants + usize::from(is_process);
+            }
+        }
+        totals.insert(pid, t);
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: u32, ppid: Option<u32>, start_time: u64) -> ProcessData {
+        ProcessData {
+            pid,
+            name: format!("p{}", pid),
+            ppid,
+            cpu_usage: 1.0,
+            memory: 10,
+            path: String::new(),
+            username: "root".to_string(),
+            is_thread: None,
+            disk_read: 0,
+            disk_write: 0,
+            cgroup: None,
+            virtual_memory: 0,
+            shared_memory: 0,
+            swap: 0,
+            start_time,
+        }
+    }
+
+    fn kids(tree: &HashMap<u32, Vec<u32>>, pid: u32) -> Vec<u32> {
+        tree.get(&pid).cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn orphans_become_roots() {
+        //parinte fantoma: 50 a murit intre timp
+        let procs = [
+            proc(1, None, 10),
+            proc(2, Some(1), 20),
+            proc(3, Some(50), 30),
+        ];
+        let (radacini, tree) = build_tree(&procs);
+        assert_eq!(radacini, [1, 3]);
+        assert_eq!(kids(&tree, 1), [2]);
+    }
+
+    #[test]
+    fn reused_parent_pid_is_not_a_parent() {
+        //parintele real (pid 7) a murit, pid-ul a fost refolosit de un proces mai nou
+        let procs = [
+            proc(1, None, 10),
+            proc(7, Some(1), 500),
+            proc(8, Some(7), 100),
+        ];
+        let (radacini, tree) = build_tree(&procs);
+        assert_eq!(radacini, [1, 8]);
+        assert_eq!(kids(&tree, 7), Vec::<u32>::new());
+
+        //fara start_time nu avem cum sa stim, pastram ppid
+        let procs = [proc(7, None, 0), proc(8, Some(7), 100)];
+        assert_eq!(build_tree(&procs).0, [7]);
+    }
+
+    #[test]
+    fn self_parented_pid_is_a_root() {
+        let procs = [proc(0, Some(0), 0), proc(5, Some(0), 0)];
+        let (radacini, tree) = build_tree(&procs);
+        assert_eq!(radacini, [0]);
+        assert_eq!(kids(&tree, 0), [5]);
+    }
+
+    #[test]
+    fn parent_cycles_are_broken() {
+        //2 <-> 3, 4 -> 5 -> 6 -> 4 cu 9 atarnat de 6, plus un arbore normal
+        let procs = [
+            proc(1, None, 0),
+            proc(9, Some(6), 0),
+            proc(2, Some(3), 0),
+            proc(3, Some(2), 0),
+            proc(4, Some(6), 0),
+            proc(5, Some(4), 0),
+            proc(6, Some(5), 0),
+        ];
+        let (radacini, tree) = build_tree(&procs);
+        assert_eq!(radacini, [1, 6, 2]);
+        assert_eq!(kids(&tree, 6), [9, 4]);
+        assert_eq!(kids(&tree, 5), Vec::<u32>::new());
+        assert_eq!(kids(&tree, 2), [3]);
+        assert_eq!(kids(&tree, 3), Vec::<u32>::new());
+
+        //fiecare proces apare o singura data in arbore
+        let mut seen = Vec::new();
+        let mut stack = radacini.clone();
+        while let Some(pid) = stack.pop() {
+            seen.push(pid);
+            stack.extend(kids(&tree, pid));
+        }
+        seen.sort();
+        assert_eq!(seen, [1, 2, 3, 4, 5, 6, 9]);
+
+        let totals = subtree_totals(&ProcessTree::build(&procs), |pid| {
+            procs.iter().find(|p| p.pid == pid)
+        });
+        assert_eq!(totals[&6].descendants, 3);
+        assert_eq!(totals[&6].memory, 40);
+    }
+
+    //(pid, parinte) sortat, ca ordinea fratilor sa nu conteze
+    fn shape(tree: &ProcessTree, procs: &[ProcessData]) -> Vec<(u32, Option<u32>)> {
+        let mut v: Vec<_> = procs.iter().map(|p| (p.pid, tree.parent(p.pid))).collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn incremental_tree_matches_rebuild() {
+        let mut procs = vec![
+            proc(1, None, 10),
+            proc(2, Some(1), 20),
+            proc(3, Some(2), 30),
+            proc(4, Some(9), 40),
+        ];
+        let mut tree = ProcessTree::build(&procs);
+        assert_eq!(tree.roots(), [1, 4]);
+
+        //parintele lui 4 apare dupa el
+        procs.push(proc(9, Some(1), 15));
+        tree.insert(9, |pid| procs.iter().find(|p| p.pid == pid));
+        assert_eq!(tree.children(9), [4]);
+        assert_eq!(
+            shape(&tree, &procs),
+            shape(&ProcessTree::build(&procs), &procs)
+        );
+
+        //2 moare, 3 ramane orfan si e preluat de 1
+        procs.retain(|p| p.pid != 2);
+        tree.remove(2);
+        assert!(tree.roots().contains(&3));
+        procs.iter_mut().find(|p| p.pid == 3).unwrap().ppid = Some(1);
+        tree.relink(3, |pid| procs.iter().find(|p| p.pid == pid));
+        assert_eq!(
+            shape(&tree, &procs),
+            shape(&ProcessTree::build(&procs), &procs)
+        );
+
+        //1 mutat sub propriul descendent ar inchide un ciclu, ramane radacina
+        procs.iter_mut().find(|p| p.pid == 1).unwrap().ppid = Some(4);
+        tree.relink(1, |pid| procs.iter().find(|p| p.pid == pid));
+        assert_eq!(tree.parent(1), None);
+        assert_eq!(tree.roots(), [1]);
+    }
+}
@@ -0,0 +1,651 @@
+//interfata de terminal pentru ssh, foloseste acelasi backend ca gui-ul
+use std::{
+    collections::HashSet,
+    io,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Gauge, Paragraph, Row, Sparkline, Table, TableState, Tabs},
+};
+
+use crate::{
+    BackendConfig, LifecycleKind, NEW_HIGHLIGHT, SortColumn, ViewMode, bytes_to_gb, bytes_to_mb,
+    delta::Mirror,
+    format_duration, format_rate, format_timestamp,
+    groups::{self, GroupBy, GroupSort},
+    handoff::{self, BackendState, SampleReceiver},
+    sort_indices,
+};
+
+fn new_style(mirror: &Mirror, pid: u32) -> Style {
+    if mirror.started_within(pid, NEW_HIGHLIGHT) {
+        Style::new().green().bold()
+    } else {
+        Style::new()
+    }
+}
+
+const SORT_ORDER: [SortColumn; 6] = [
+    SortColumn::Pid,
+    SortColumn::Name,
+    SortColumn::Cpu,
+    SortColumn::Memory,
+    SortColumn::DiskRead,
+    SortColumn::DiskWrite,
+];
+
+struct TuiApp {
+    rx: SampleReceiver,
+    interval: Duration,
+    view: ViewMode,
+    mirror: Mirror,
+    show_threads: bool,
+    sort_col: SortColumn,
+    sort_desc: bool,
+    paused: bool,
+
+    //tree
+    collapsed: HashSet<u32>,
+
+    list_state: TableState,
+    tree_state: TableState,
+    groups_state: TableState,
+    events_state: TableState,
+    group_by: GroupBy,
+    //None = toate evenimentele
+    event_kind: Option<LifecycleKind>,
+}
+
+pub fn run(mut config: BackendConfig) -> io::Result<()> {
+    let interval = config.interval;
+    //tabelul din tui nu are coloane shared/swap
+    config.process_memory = false;
+    let rx = handoff::spawn_backend(Arc::new(Mutex::new(config)), || {});
+
+    let mut app = TuiApp {
+        rx,
+        interval,
+        view: ViewMode::List,
+        mirror: Mirror::new(),
+        show_threads: false,
+        sort_col: SortColumn::Cpu,
+        sort_desc: true,
+        paused: false,
+        collapsed: HashSet::new(),
+        list_state: TableState::default().with_selected(0),
+        tree_state: TableState::default().with_selected(0),
+        groups_state: TableState::default().with_selected(0),
+        events_state: TableState::default().with_selected(0),
+        group_by: GroupBy::Cgroup,
+        event_kind: None,
+    };
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl TuiApp {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            //in pauza nu citim, handoff-ul comaseaza delta-urile pana la reluare
+            if !self.paused
+                && let Some(delta) = self.rx.try_recv()
+            {
+                self.mirror.apply(delta);
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if event::poll(Duration::from_millis(200))?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle_key(key.code)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    //false = iesire
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('1') => self.view = ViewMode::List,
+            KeyCode::Char('2') => self.view = ViewMode::Tree,
+            KeyCode::Char('3') => self.view = ViewMode::Overview,
+            KeyCode::Char('4') => self.view = ViewMode::Groups,
+            KeyCode::Char('5') => self.view = ViewMode::Events,
+            KeyCode::Tab => {
+                self.view = match self.view {
+                    ViewMode::List => ViewMode::Tree,
+                    ViewMode::Tree => ViewMode::Overview,
+                    ViewMode::Overview => ViewMode::Groups,
+                    ViewMode::Groups => ViewMode::Events,
+                    ViewMode::Events => ViewMode::List,
+                }
+            }
+            KeyCode::Char('t') => self.show_threads = !self.show_threads,
+            KeyCode::Char('s') => {
+                let i = SORT_ORDER
+                    .iter()
+                    .position(|&c| c == self.sort_col)
+                    .unwrap_or(0);
+                self.sort_col = SORT_ORDER[(i + 1) % SORT_ORDER.len()];
+                self.sort_desc = true;
+            }
+            KeyCode::Char('r') => self.sort_desc = !self.sort_desc,
+            KeyCode::Char('g') => {
+                let i = GroupBy::ALL
+                    .iter()
+                    .position(|&b| b == self.group_by)
+                    .unwrap_or(0);
+                self.group_by = GroupBy::ALL[(i + 1) % GroupBy::ALL.len()];
+            }
+            KeyCode::Char('e') => {
+                let filters = LifecycleKind::FILTERS;
+                let i = filters
+                    .iter()
+                    .position(|&k| k == self.event_kind)
+                    .unwrap_or(0);
+                self.event_kind = filters[(i + 1) % filters.len()];
+            }
+            KeyCode::Char('p') => {
+                self.paused = !self.paused;
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::PageDown => self.move_cursor(20),
+            KeyCode::PageUp => self.move_cursor(-20),
+            KeyCode::Enter | KeyCode::Char(' ') => self.toggle_collapsed(),
+            _ => {}
+        }
+        true
+    }
+
+    fn move_cursor(&mut self, delta: i32) {
+        let state = match self.view {
+            ViewMode::List => &mut self.list_state,
+            ViewMode::Tree => &mut self.tree_state,
+            ViewMode::Groups => &mut self.groups_state,
+            ViewMode::Events => &mut self.events_state,
+            ViewMode::Overview => return,
+        };
+        let cur = state.selected().unwrap_or(0) as i32;
+        state.select(Some((cur + delta).max(0) as usize));
+    }
+
+    fn toggle_collapsed(&mut self) {
+        if self.view != ViewMode::Tree {
+            return;
+        }
+        let rows = self.tree_rows();
+        let Some(&(pid, _)) = self.tree_state.selected().and_then(|i| rows.get(i)) else {
+            return;
+        };
+        if self.collapsed.contains(&pid) {
+            self.collapsed.remove(&pid);
+        } else {
+            self.collapsed.insert(pid);
+        }
+    }
+
+    fn health_line(&self) -> Line<'static> {
+        let health = self.rx.health();
+        match (&health.state, health.since_last_sample) {
+            (BackendState::Running, None) => Line::from(" waiting ").dark_gray(),
+            (BackendState::Running, Some(d)) if health.is_stale(self.interval) => {
+                Line::from(format!(" stale, {} ago ", format_duration(d.as_secs()))).yellow()
+            }
+            (BackendState::Running, Some(d)) => {
+                Line::from(format!(" ● {:.1}s ago ", d.as_secs_f32())).green()
+            }
+            (state, _) => Line::from(format!(" ✖ {} ", state)).red().bold(),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let selected = match self.view {
+            ViewMode::List => 0,
+            ViewMode::Tree => 1,
+            ViewMode::Overview => 2,
+            ViewMode::Groups => 3,
+            ViewMode::Events => 4,
+        };
+        frame.render_widget(
+            Tabs::new([
+                "1 List View",
+                "2 Tree View",
+                "3 Overview",
+                "4 Groups",
+                "5 Events",
+            ])
+            .select(selected)
+            .highlight_style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .block(
+                Block::bordered()
+                    .title(" Process Monitor ")
+                    .title_top(self.health_line().right_aligned()),
+            ),
+            header,
+        );
+
+        let sort_name = match self.sort_col {
+            SortColumn::Pid => "PID",
+            SortColumn::Name => "Name",
+            SortColumn::Cpu => "CPU",
+            SortColumn::Memory => "Mem",
+            SortColumn::DiskRead => "Read",
+            SortColumn::DiskWrite => "Write",
+            SortColumn::VirtualMemory => "Virt",
+            SortColumn::SharedMemory => "Shared",
+            SortColumn::Swap => "Swap",
+        };
+        frame.render_widget(
+            Line::from(format!(
+                " q quit | tab/1-5 view | t threads ({}) | s sort: {} {} | r reverse | enter collapse | g group by | e events: {} | p pause{}",
+                if self.show_threads { "on" } else { "off" },
+                sort_name,
+                if self.sort_desc { "⬇" } else { "⬆" },
+                LifecycleKind::filter_label(self.event_kind),
+                if self.paused { " (PAUSED)" } else { "" },
+            ))
+            .dark_gray(),
+            footer,
+        );
+
+        if self.mirror.data().is_none() {
+            frame.render_widget(Paragraph::new("waiting for first sample..."), body);
+            return;
+        }
+        match self.view {
+            ViewMode::List => self.draw_list(frame, body),
+            ViewMode::Tree => self.draw_tree(frame, body),
+            ViewMode::Overview => self.draw_overview(frame, body),
+            ViewMode::Groups => self.draw_groups(frame, body),
+            ViewMode::Events => self.draw_events(frame, body),
+        }
+    }
+
+    //pornirile si iesirile de procese, cele mai noi primele (e filtreaza dupa tip)
+    fn draw_events(&mut self, frame: &mut Frame, area: Rect) {
+        let events: Vec<_> = self
+            .mirror
+            .events()
+            .iter()
+            .rev()
+            .filter(|e| e.matches(self.event_kind, None))
+            .collect();
+        let rows = events.iter().map(|e| {
+            let p = &e.process;
+            let kind = match e.kind {
+                LifecycleKind::Started => Line::from("STARTED").green(),
+                LifecycleKind::Exited => Line::from("EXITED").red(),
+            };
+            Row::new([
+                Line::from(format_timestamp(e.timestamp_ms / 1000)),
+                kind,
+                Line::from(p.pid.to_string()),
+                Line::from(p.name.clone()),
+                Line::from(p.ppid.map_or("-".to_string(), |pp| pp.to_string())),
+                Line::from(format!("{:.1}", p.cpu_usage)),
+                Line::from(format!("{:.2} MB", bytes_to_mb(p.memory))),
+                Line::from(p.path.clone()),
+            ])
+        });
+        let widths = [
+            Constraint::Length(20),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(20),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Min(10),
+        ];
+        clamp_selection(&mut self.events_state, events.len());
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new([
+                    "Time", "Event", "PID", "Name", "PPID", "CPU %", "Mem", "Path",
+                ])
+                .bold()
+                .underlined(),
+            )
+            .row_highlight_style(Style::new().reversed())
+            .block(Block::new().borders(Borders::NONE));
+        frame.render_stateful_widget(table, area, &mut self.events_state);
+    }
+
+    //grupurile (g schimba criteriul), sortate dupa coloana listei
+    fn draw_groups(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(data) = self.mirror.data() else {
+            return;
+        };
+        let mut groups = groups::group(&data.procese, self.group_by, None);
+        let sort = match self.sort_col {
+            SortColumn::Memory => GroupSort::Memory,
+            SortColumn::Name => GroupSort::Name,
+            _ => GroupSort::Cpu,
+        };
+        groups::sort_groups(&mut groups, sort, self.sort_desc);
+
+        let rows = groups.iter().map(|g| {
+            Row::new([
+                g.name.clone(),
+                g.kind.clone(),
+                g.members.len().to_string(),
+                g.threads.to_string(),
+                format!("{:.1}", g.cpu),
+                format!("{:.2} MB", bytes_to_mb(g.memory)),
+            ])
+        });
+        let widths = [
+            Constraint::Min(20),
+            Constraint::Length(12),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(12),
+        ];
+        clamp_selection(&mut self.groups_state, groups.len());
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new([
+                    self.group_by.label(),
+                    "Kind",
+                    "Procs",
+                    "Threads",
+                    "CPU %",
+                    "Mem",
+                ])
+                .bold()
+                .underlined(),
+            )
+            .row_highlight_style(Style::new().reversed())
+            .block(Block::new().borders(Borders::NONE));
+        frame.render_stateful_widget(table, area, &mut self.groups_state);
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(data) = self.mirror.data() else {
+            return;
+        };
+        let processes = &data.procese;
+        let mut indices: Vec<usize> = processes
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| self.show_threads || p.is_thread.is_none())
+            .map(|(i, _)| i)
+            .collect();
+        sort_indices(processes, &mut indices, self.sort_col, self.sort_desc);
+
+        let rows = indices.iter().map(|&i| {
+            let p = &processes[i];
+            Row::new([
+                p.pid.to_string(),
+                p.name.clone(),
+                format!("{:.1}", p.cpu_usage),
+                format!("{:.2} MB", bytes_to_mb(p.memory)),
+                format_rate(p.disk_read as f64),
+                format_rate(p.disk_write as f64),
+                p.username.clone(),
+                p.path.clone(),
+            ])
+            .style(new_style(&self.mirror, p.pid))
+        });
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Length(20),
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Min(10),
+        ];
+        clamp_selection(&mut self.list_state, indices.len());
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new([
+                    "PID", "Name", "CPU %", "Mem", "Read", "Write", "User", "Path",
+                ])
+                .bold()
+                .underlined(),
+            )
+            .row_highlight_style(Style::new().reversed())
+            .block(Block::new().borders(Borders::NONE));
+        frame.render_stateful_widget(table, area, &mut self.list_state);
+    }
+
+    //arborele aplatizat in randuri (pid, adancime), fara copiii nodurilor inchise.
+    //doar randurile desenate, selectia se indexeaza in aceeasi lista
+    fn tree_rows(&self) -> Vec<(u32, usize)> {
+        let mut rows = Vec::new();
+        let tree = self.mirror.tree();
+        let mut stack: Vec<(u32, usize)> = tree.roots().iter().rev().map(|&p| (p, 0)).collect();
+        while let Some((pid, depth)) = stack.pop() {
+            if self.mirror.get(pid).is_some() {
+                rows.push((pid, depth));
+            }
+            if self.collapsed.contains(&pid) {
+                continue;
+            }
+            stack.extend(tree.children(pid).iter().rev().map(|&k| (k, depth + 1)));
+        }
+        rows
+    }
+
+    fn draw_tree(&mut self, frame: &mut Frame, area: Rect) {
+        if self.mirror.data().is_none() {
+            return;
+        }
+        let tree_rows = self.tree_rows();
+        let rows = tree_rows.iter().filter_map(|&(pid, depth)| {
+            //tree_rows a sarit deja pid-urile fara proces
+            let p = self.mirror.get(pid)?;
+            let marker = match self.mirror.tree().children(pid) {
+                kids if !kids.is_empty() => {
+                    if self.collapsed.contains(&pid) {
+                        "▸ "
+                    } else {
+                        "▾ "
+                    }
+                }
+                _ => "  ",
+            };
+            Some(
+                Row::new([format!(
+                    "{}{}{} [{}] ({:.1}%)",
+                    "  ".repeat(depth),
+                    marker,
+                    p.name,
+                    pid,
+                    p.cpu_usage
+                )])
+                .style(new_style(&self.mirror, pid)),
+            )
+        });
+        clamp_selection(&mut self.tree_state, tree_rows.len());
+        let table = Table::new(rows, [Constraint::Percentage(100)])
+            .row_highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(table, area, &mut self.tree_state);
+    }
+
+    fn draw_overview(&self, frame: &mut Frame, area: Rect) {
+        let Some(data) = self.mirror.data() else {
+            return;
+        };
+        let global = &data.stats;
+        let [info, gauges, graph, bottom] = Layout::vertical([
+            Constraint::Length(5),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+
+        let info_text = vec![
+            Line::from(global.distribution_id.clone()).bold(),
+            Line::from(format!(
+                "Host: {}   Arch: {}   Cores: {}",
+                global.host_name.as_deref().unwrap_or(""),
+                global.cpu_architecture,
+                global.cores
+            )),
+            Line::from(format!(
+                "OS: {} {}   Uptime: {}",
+                global.system_name.as_deref().unwrap_or(""),
+                global.os_version.as_deref().unwrap_or(""),
+                format_duration(global.uptime)
+            )),
+            Line::from(format!(
+                "Load: {:.2} {:.2} {:.2}   Procs: {} ({} threads)   {} running, {} sleeping, {} zombie",
+                global.load_average[0],
+                global.load_average[1],
+                global.load_average[2],
+                global.process_count,
+                global.thread_count,
+                global.running,
+                global.sleeping,
+                global.zombies
+            )),
+            Line::from(format!("Kernel: {}", global.kernel_long_version)),
+        ];
+        frame.render_widget(Paragraph::new(info_text).block(Block::bordered()), info);
+
+        let mem_perc = if global.total_memory > 0 {
+            global.used_memory as f64 / global.total_memory as f64
+        } else {
+            0.0
+        };
+        let swap_perc = if global.total_swap > 0 {
+            global.used_swap as f64 / global.total_swap as f64
+        } else {
+            0.0
+        };
+        let [cpu_area, mem_area, swap_area] =
+            Layout::vertical([Constraint::Length(3); 3]).areas(gauges);
+        let gauge = |title: &str, ratio: f64, label: String, color: Color| {
+            Gauge::default()
+                .block(Block::bordered().title(format!(" {} ", title)))
+                .gauge_style(Style::new().fg(color))
+                .ratio(ratio.clamp(0.0, 1.0))
+                .label(label)
+        };
+        frame.render_widget(
+            gauge(
+                "PROCESSOR",
+                global.global_cpu_usage as f64 / 100.0,
+                format!("{:.1}%", global.global_cpu_usage),
+                Color::Cyan,
+            ),
+            cpu_area,
+        );
+        frame.render_widget(
+            gauge(
+                "MEMORY",
+                mem_perc,
+                format!(
+                    "{:.1}/{:.1} GB",
+                    bytes_to_gb(global.used_memory),
+                    bytes_to_gb(global.total_memory)
+                ),
+                Color::Magenta,
+            ),
+            mem_area,
+        );
+        frame.render_widget(
+            gauge(
+                "SWAP",
+                swap_perc,
+                format!(
+                    "{:.1}/{:.1} GB",
+                    bytes_to_gb(global.used_swap),
+                    bytes_to_gb(global.total_swap)
+                ),
+                Color::Yellow,
+            ),
+            swap_area,
+        );
+
+        let cpu_points: Vec<u64> = global.cpu_history.iter().map(|&v| v as u64).collect();
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::bordered().title(" CPU history "))
+                .data(&cpu_points)
+                .max(100)
+                .style(Style::new().fg(Color::Cyan)),
+            graph,
+        );
+
+        let [disks_area, net_area] =
+            Layout::horizontal([Constraint::Percentage(50); 2]).areas(bottom);
+        let disk_rows = global.disks.iter().map(|d| {
+            let used = d.total_space.saturating_sub(d.available_space);
+            Row::new([
+                d.mount_point.clone(),
+                d.file_system.clone(),
+                format!(
+                    "{:.1}/{:.1} GB",
+                    bytes_to_gb(used),
+                    bytes_to_gb(d.total_space)
+                ),
+            ])
+        });
+        frame.render_widget(
+            Table::new(
+                disk_rows,
+                [
+                    Constraint::Min(10),
+                    Constraint::Length(8),
+                    Constraint::Length(16),
+                ],
+            )
+            .block(Block::bordered().title(" DISKS ")),
+            disks_area,
+        );
+        let net_rows = global.networks.iter().map(|n| {
+            Row::new([
+                n.name.clone(),
+                format!("↓ {}", format_rate(n.rx_rate)),
+                format!("↑ {}", format_rate(n.tx_rate)),
+            ])
+        });
+        frame.render_widget(
+            Table::new(
+                net_rows,
+                [
+                    Constraint::Min(8),
+                    Constraint::Length(14),
+                    Constraint::Length(14),
+                ],
+            )
+            .block(Block::bordered().title(" NETWORK ")),
+            net_area,
+        );
+    }
+}
+
+fn clamp_selection(state: &mut TableState, len: usize) {
+    if len == 0 {
+        state.select(None);
+    } else {
+        state.select(Some(state.selected().unwrap_or(0).min(len - 1)));
+    }
+}